@@ -0,0 +1,76 @@
+use crate::{
+    Numeric,
+    drag::DragTable,
+    error::{Error, Result},
+    units::{Area, ArealMassDensity, area::square_inch, mass::pound},
+};
+
+// Points the mach range is sampled at when fitting
+const SAMPLES: usize = 200;
+
+// Fitted ballistic coefficient, with the root mean square difference in deceleration over the
+// range fitted, relative to the mean deceleration being matched (0.0 is an exact match)
+#[derive(Debug, Clone, Copy)]
+pub struct Fit {
+    pub value: Numeric,
+    pub residual: Numeric,
+}
+
+// Form factor of a projectile with drag coefficient cd at mach, against standard T
+pub fn form_factor<T>(mach: Numeric, cd: Numeric) -> Numeric
+where
+    T: DragTable,
+{
+    cd / T::lookup(mach)
+}
+
+// Ballistic coefficient (lb/in^2) from sectional density and form factor
+pub fn bc_from_form_factor(sd: ArealMassDensity, form_factor: Numeric) -> Result<Numeric> {
+    if form_factor > 0.0 {
+        let sd = (sd * Area::new::<square_inch>(1.0)).get::<pound>();
+        Ok(sd / form_factor)
+    } else {
+        Err(Error::PositiveExpected { value: form_factor })
+    }
+}
+
+// Ballistic coefficient against standard T matching bc against standard F, over mach min to max
+// Deceleration is proportional to cd(mach) / bc, so the fit is least squares on
+// cd_F(M) / bc_F - cd_T(M) * x, solved in closed form for x = 1 / bc_T
+pub fn convert<F, T>(bc: Numeric, min: Numeric, max: Numeric) -> Result<Fit>
+where
+    F: DragTable,
+    T: DragTable,
+{
+    if bc <= 0.0 {
+        return Err(Error::PositiveExpected { value: bc });
+    }
+    if min < 0.0 || min >= max {
+        return Err(Error::NumericOutOfRange {
+            value: min,
+            min: 0.0,
+            max,
+        });
+    }
+    let samples = (0..SAMPLES)
+        .map(|i| min + (max - min) * i as Numeric / (SAMPLES - 1) as Numeric)
+        .map(|mach| (F::lookup(mach) / bc, T::lookup(mach)))
+        .collect::<Vec<_>>();
+
+    let (num, den) = samples.iter().fold((0.0, 0.0), |(num, den), (from, to)| {
+        (num + from * to, den + to * to)
+    });
+    let x = num / den;
+
+    let mean = samples.iter().map(|(from, _)| from).sum::<Numeric>() / SAMPLES as Numeric;
+    let squares = samples
+        .iter()
+        .map(|(from, to)| (from - to * x).powi(2))
+        .sum::<Numeric>();
+    let residual = (squares / SAMPLES as Numeric).sqrt() / mean;
+
+    Ok(Fit {
+        value: 1.0 / x,
+        residual,
+    })
+}
@@ -1,10 +1,20 @@
 pub use crate::{
+    Numeric,
     physics::{DragFunction, DragInit},
     units::{ReciprocalLength, Velocity},
 };
 
-use std::ops::{Add, Div, Mul, Sub};
+use crate::{
+    error::{Error, Result},
+    units::{reciprocal_length::reciprocal_meter, velocity::meter_per_second},
+};
+
+use std::{
+    marker::PhantomData,
+    ops::{Add, Div, Mul, Sub},
+};
 
+pub mod custom;
 pub mod g1;
 pub mod g2;
 pub mod g5;
@@ -34,15 +44,22 @@ macro_rules! table {
 
         use $crate::{
             consts::FRAC_PI_8,
+            error::Result,
             simulation::Simulation,
         };
 
+        const N: usize = count!($($x),*);
+        const MACH: [Numeric; N] = [$($x),*];
+
+        // Lookups binary search mach, so a misordered entry would silently corrupt them
+        const _: () = assert!(ascending(&MACH), "drag table mach must be strictly ascending");
+
         #[derive(Debug)]
-        pub struct Drag(Table<{ count!($($x),*) }, Velocity, ReciprocalLength>);
+        pub struct Drag<I = Linear>(Table<N, Velocity, ReciprocalLength>, PhantomData<I>);
 
-        impl DragInit for Drag {
-            fn new(simulation: &Simulation<Self>) -> Self {
-                Self(Table {
+        impl<I> DragInit for Drag<I> {
+            fn new(simulation: &Simulation<Self>) -> Result<Self> {
+                Ok(Self(Table {
                     x: [
                         $(
                             $x * simulation.atmosphere.sound_velocity()
@@ -53,19 +70,149 @@ macro_rules! table {
                             -($y * FRAC_PI_8) * simulation.atmosphere.rho() / simulation.projectile.bc()
                         ),*
                     ],
-                })
+                }, PhantomData))
             }
         }
 
-        impl DragFunction for Drag {
+        impl<I> DragFunction for Drag<I>
+        where
+            I: Interpolation,
+        {
             fn cd(&self, velocity: Velocity) -> ReciprocalLength {
-                self.0.lerp(velocity)
+                I::interpolate(&self.0.x, &self.0.y, velocity)
             }
         }
+
+        impl<I> DragTable for Drag<I> {
+            const MACH: &'static [Numeric] = &MACH;
+            const CD: &'static [Numeric] = &[$($y),*];
+        }
     };
 }
 use table;
 
+// Whether each value is greater than the one before, without duplicates
+// table! asserts this of its mach column at compile time, so a misordered table fails to build
+/// ```
+/// use point_mass_ballistics::drag::ascending;
+///
+/// const _: () = assert!(ascending(&[0.95, 1.00, 1.04]));
+/// ```
+///
+/// ```compile_fail
+/// use point_mass_ballistics::drag::ascending;
+///
+/// const _: () = assert!(ascending(&[0.95, 1.04, 1.00]));
+/// ```
+pub const fn ascending(values: &[Numeric]) -> bool {
+    let mut i = 1;
+    while i < values.len() {
+        if values[i - 1] >= values[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+// Scheme used to interpolate drag tables between their knots, given as the drag model's type
+// parameter, as in g7::Drag<Pchip>
+// Linear: piecewise linear, with kinks in drag at every knot
+// Pchip: piecewise cubic hermite with slopes limited to preserve monotonicity (Fritsch-Carlson),
+// smooth through the transonic rise without overshooting it
+// Both hold the end values beyond the table
+pub trait Interpolation {
+    fn interpolate(x: &[Velocity], y: &[ReciprocalLength], velocity: Velocity) -> ReciprocalLength;
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Linear;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Pchip;
+
+impl Interpolation for Linear {
+    fn interpolate(x: &[Velocity], y: &[ReciprocalLength], velocity: Velocity) -> ReciprocalLength {
+        lerp(x, y, velocity)
+    }
+}
+
+impl Interpolation for Pchip {
+    fn interpolate(x: &[Velocity], y: &[ReciprocalLength], velocity: Velocity) -> ReciprocalLength {
+        let j = search(x, velocity);
+        if j == 0 {
+            return y[0];
+        }
+        if j == x.len() {
+            return y[j - 1];
+        }
+        let i = j - 1;
+        let (x0, x1) = (
+            x[i].get::<meter_per_second>(),
+            x[j].get::<meter_per_second>(),
+        );
+        let (y0, y1) = (
+            y[i].get::<reciprocal_meter>(),
+            y[j].get::<reciprocal_meter>(),
+        );
+        let h = x1 - x0;
+        let t = (velocity.get::<meter_per_second>() - x0) / h;
+        let (d0, d1) = (pchip_slope(x, y, i), pchip_slope(x, y, j));
+
+        // Cubic hermite basis
+        let (t2, t3) = (t * t, t * t * t);
+        let value = (2.0 * t3 - 3.0 * t2 + 1.0) * y0
+            + (t3 - 2.0 * t2 + t) * h * d0
+            + (-2.0 * t3 + 3.0 * t2) * y1
+            + (t3 - t2) * h * d1;
+        ReciprocalLength::new::<reciprocal_meter>(value)
+    }
+}
+
+// Slope at knot k, the weighted harmonic mean of the adjacent secants, or zero at an extremum
+// At the ends, a one sided three point estimate, limited so as not to overshoot
+fn pchip_slope(x: &[Velocity], y: &[ReciprocalLength], k: usize) -> Numeric {
+    let n = x.len();
+    let width = |i: usize| (x[i + 1] - x[i]).get::<meter_per_second>();
+    let secant = |i: usize| (y[i + 1] - y[i]).get::<reciprocal_meter>() / width(i);
+    if n == 2 {
+        return secant(0);
+    }
+    let end = |(h0, h1, s0, s1): (Numeric, Numeric, Numeric, Numeric)| {
+        let d = ((2.0 * h0 + h1) * s0 - h0 * s1) / (h0 + h1);
+        if d.signum() != s0.signum() {
+            0.0
+        } else if s0.signum() != s1.signum() && d.abs() > (3.0 * s0).abs() {
+            3.0 * s0
+        } else {
+            d
+        }
+    };
+    if k == 0 {
+        end((width(0), width(1), secant(0), secant(1)))
+    } else if k == n - 1 {
+        end((width(n - 2), width(n - 3), secant(n - 2), secant(n - 3)))
+    } else {
+        let (h0, h1, s0, s1) = (width(k - 1), width(k), secant(k - 1), secant(k));
+        if s0 * s1 <= 0.0 {
+            0.0
+        } else {
+            let (w0, w1) = (2.0 * h1 + h0, h1 + 2.0 * h0);
+            (w0 + w1) / (w0 / s0 + w1 / s1)
+        }
+    }
+}
+
+// Unscaled drag curve of a standard projectile, cd over ascending mach
+pub trait DragTable {
+    const MACH: &'static [Numeric];
+    const CD: &'static [Numeric];
+
+    fn lookup(mach: Numeric) -> Numeric {
+        lerp(Self::MACH, Self::CD, mach)
+    }
+}
+
 #[derive(Debug)]
 pub struct Table<const N: usize, X, Y> {
     x: [X; N],
@@ -78,17 +225,40 @@ impl<const N: usize, X, Y> Table<N, X, Y> {
         X: Copy + PartialOrd + Sub<Output = X>,
         Y: Copy + Sub<Output = Y> + Add<Output = Y> + Div<X, Output: Mul<X, Output = Y>>,
     {
-        let j = search(&self.x, x);
-        if j == 0 {
-            return self.y[j];
-        }
-        let i = j - 1;
-        if j == N {
-            return self.y[i];
-        }
-        let (x0, y0, x1, y1) = (self.x[i], self.y[i], self.x[j], self.y[j]);
-        y0 + ((y1 - y0) / (x1 - x0)) * (x - x0)
+        lerp(&self.x, &self.y, x)
+    }
+}
+
+// Rows of a table given at runtime, at least two, over strictly ascending x
+pub(crate) fn validate_table<X: PartialOrd, Y>(table: &[(X, Y)]) -> Result<()> {
+    if table.len() < 2 {
+        return Err(Error::TableTooShort {
+            length: table.len(),
+            min: 2,
+        });
+    }
+    if let Some(index) = table.windows(2).position(|pair| pair[0].0 >= pair[1].0) {
+        return Err(Error::TableUnsorted { index: index + 1 });
+    }
+    Ok(())
+}
+
+// Linear interpolation over ascending xs, clamped to the ends
+pub fn lerp<X, Y>(xs: &[X], ys: &[Y], x: X) -> Y
+where
+    X: Copy + PartialOrd + Sub<Output = X>,
+    Y: Copy + Sub<Output = Y> + Add<Output = Y> + Div<X, Output: Mul<X, Output = Y>>,
+{
+    let j = search(xs, x);
+    if j == 0 {
+        return ys[j];
+    }
+    let i = j - 1;
+    if j == xs.len() {
+        return ys[i];
     }
+    let (x0, y0, x1, y1) = (xs[i], ys[i], xs[j], ys[j]);
+    y0 + ((y1 - y0) / (x1 - x0)) * (x - x0)
 }
 
 pub fn search<T>(slice: &[T], x: T) -> usize
@@ -0,0 +1,81 @@
+use super::*;
+
+use crate::{
+    Numeric,
+    consts::FRAC_PI_8,
+    error::{Error, Result},
+    simulation::Simulation,
+};
+
+// Drag curve given at runtime, such as a manufacturer's custom drag model or one derived from radar
+// Cd is over ascending mach, and is prescaled at init like the built in tables, so for a curve
+// measured on the projectile itself the bc should be its sectional density (form factor of 1)
+// Given to the builder through set_drag
+#[derive(Debug, Clone)]
+pub struct Drag<I = Linear> {
+    mach: Vec<Numeric>,
+    cd: Vec<Numeric>,
+    x: Vec<Velocity>,
+    y: Vec<ReciprocalLength>,
+    interpolation: PhantomData<I>,
+}
+
+impl<I> Drag<I> {
+    pub fn from_table(table: &[(Numeric, Numeric)]) -> Result<Self> {
+        validate_table(table)?;
+        // NaN passes both the order and sign checks, so is caught along with infinity here
+        if let Some(value) = table
+            .iter()
+            .flat_map(|&(mach, cd)| [mach, cd])
+            .find(|value| !value.is_finite())
+        {
+            return Err(Error::NumericOutOfRange {
+                value,
+                min: 0.0,
+                max: Numeric::MAX,
+            });
+        }
+        if let Some(&(mach, cd)) = table.iter().find(|(mach, cd)| *mach < 0.0 || *cd < 0.0) {
+            return Err(Error::PositiveExpected {
+                value: if mach < 0.0 { mach } else { cd },
+            });
+        }
+        Ok(Self {
+            mach: table.iter().map(|&(mach, _)| mach).collect(),
+            cd: table.iter().map(|&(_, cd)| cd).collect(),
+            x: Vec::new(),
+            y: Vec::new(),
+            interpolation: PhantomData,
+        })
+    }
+}
+
+impl<I> DragInit for Drag<I> {
+    fn new(simulation: &Simulation<Self>) -> Result<Self> {
+        let Self { mach, cd, .. } = simulation.drag.as_ref().ok_or(Error::DragNotSet)?;
+        Ok(Self {
+            mach: mach.clone(),
+            cd: cd.clone(),
+            x: mach
+                .iter()
+                .map(|&mach| mach * simulation.atmosphere.sound_velocity())
+                .collect(),
+            y: cd
+                .iter()
+                .map(|&cd| {
+                    -(cd * FRAC_PI_8) * simulation.atmosphere.rho() / simulation.projectile.bc()
+                })
+                .collect(),
+            interpolation: PhantomData,
+        })
+    }
+}
+
+impl<I> DragFunction for Drag<I>
+where
+    I: Interpolation,
+{
+    fn cd(&self, velocity: Velocity) -> ReciprocalLength {
+        I::interpolate(&self.x, &self.y, velocity)
+    }
+}
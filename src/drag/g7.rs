@@ -27,7 +27,7 @@ table![
     0.9254 => 0.1660,
     0.954  => 0.2054,
     0.9754 => 0.2993,
-    1.04   => 0.3803,
+    1.00   => 0.3803,
     1.0254 => 0.4015,
     1.054  => 0.4043,
     1.0754 => 0.4034,
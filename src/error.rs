@@ -1,6 +1,6 @@
 use crate::{
     Numeric,
-    units::{Angle, ThermodynamicTemperature, Time},
+    units::{Angle, Length, ThermodynamicTemperature, Time},
 };
 
 use std::{error, fmt, result};
@@ -25,6 +25,11 @@ pub enum Error {
         min: Angle,
         max: Angle,
     },
+    LengthOutOfRange {
+        value: Length,
+        min: Length,
+        max: Length,
+    },
     ThermodynamicTemperatureOutOfRange {
         value: ThermodynamicTemperature,
         min: ThermodynamicTemperature,
@@ -35,6 +40,24 @@ pub enum Error {
         min: Time,
         max: Time,
     },
+    Parse {
+        line: usize,
+    },
+    TableTooShort {
+        length: usize,
+        min: usize,
+    },
+    TableUnsorted {
+        index: usize,
+    },
+    Unstable {
+        stability: Numeric,
+    },
+    DragNotSet,
+    Conflict {
+        first: &'static str,
+        second: &'static str,
+    },
     ZeroAngleOutOfRange {
         count: u64,
         pitch: Angle,
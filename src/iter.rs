@@ -1,6 +1,7 @@
 use crate::{
     output::Packet,
-    physics::DragFunction,
+    physics::{Attitude, DragFunction},
+    random::Rng,
     simulation::Simulation,
     units::{ConstZero, Time, length, typenum::P2, velocity},
     vectors::MyVector3,
@@ -11,18 +12,32 @@ use std::iter::FusedIterator;
 #[derive(Debug)]
 pub struct Iter<'a, D> {
     simulation: &'a Simulation<D>,
+    // Muzzle velocity, fixed for the run, so its powder and barrel adjustments are looked up once
+    muzzle: MyVector3<velocity::Dimension>,
     position: MyVector3<length::Dimension>,
     velocity: MyVector3<velocity::Dimension>,
+    gust: MyVector3<velocity::Dimension>,
+    coriolis: MyVector3<length::Dimension>,
+    coriolis_velocity: MyVector3<velocity::Dimension>,
+    attitude: Attitude,
     time: Time,
+    rng: Rng,
 }
 
 impl<D> Simulation<D> {
     pub fn iter(&self) -> Iter<'_, D> {
+        let mut rng = Rng::new(self.atmosphere.wind.gust.seed);
         Iter {
             simulation: self,
+            muzzle: self.velocity(),
             position: MyVector3::ZERO,
             velocity: MyVector3::ZERO,
+            gust: self.initial_gust(&mut rng),
+            coriolis: MyVector3::ZERO,
+            coriolis_velocity: MyVector3::ZERO,
+            attitude: self.initial_attitude(),
             time: Time::ZERO,
+            rng,
         }
     }
 }
@@ -50,15 +65,21 @@ where
         // Previous values captured to be returned, so that time 0 can be accounted for
         let &mut Self {
             simulation,
+            muzzle,
             position,
             velocity,
+            gust,
+            coriolis,
+            coriolis_velocity,
+            attitude,
             time,
+            ..
         } = self;
 
-        let v = simulation.velocity() + velocity;
+        let v = muzzle + velocity;
         let dt = simulation.time_step;
         let dt_sq = dt.powi(P2::new());
-        let a = simulation.acceleration(v);
+        let a = simulation.acceleration(position, v, gust, &attitude);
 
         // Second Equation of Motion
         let dp = v * dt + a * dt_sq * 0.5;
@@ -66,15 +87,30 @@ where
         // First Equation of Motion
         let dv = a * dt;
 
+        // Earth's rotation alone, integrated alongside to break its share out of the totals
+        // Drag also acts on the velocity it adds, so the difference in drag is included
+        // Skipped when off, as it stays zero and the extra drag evaluations aren't free
+        if simulation.flags.coriolis {
+            let ac = simulation.coriolis_acceleration(position, v)
+                + simulation.drag_acceleration(position, v, gust)
+                - simulation.drag_acceleration(position, v - coriolis_velocity, gust);
+            self.coriolis += coriolis_velocity * dt + ac * dt_sq * 0.5;
+            self.coriolis_velocity += ac * dt;
+        }
+
         self.time += dt;
         self.position += dp;
         self.velocity += dv;
+        self.attitude = simulation.rotate(position, v, gust, &attitude);
+        self.gust = simulation.gust_velocity(gust, &mut self.rng);
 
         Some(Self::Item {
             simulation,
             time,
             position,
             velocity,
+            gust,
+            coriolis,
             acceleration: a,
         })
     }
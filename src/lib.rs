@@ -4,13 +4,16 @@ mod consts {
     pub use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, FRAC_PI_8, PI};
 }
 mod physics;
+mod random;
 mod vectors;
 
+pub mod bc;
 #[allow(clippy::approx_constant)]
 pub mod drag;
 pub mod error;
 pub mod iter;
 pub mod output;
+pub mod radar;
 pub mod simulation;
 pub mod units;
 pub mod solvers {
@@ -32,9 +32,44 @@ pub struct Packet<'a, D> {
     pub(crate) time: Time,
     pub(crate) position: MyVector3<length::Dimension>,
     pub(crate) velocity: MyVector3<velocity::Dimension>,
+    pub(crate) gust: MyVector3<velocity::Dimension>,
+    pub(crate) coriolis: MyVector3<length::Dimension>,
     pub(crate) acceleration: MyVector3<acceleration::Dimension>,
 }
 
+impl<D> Packet<'_, D>
+where
+    D: DragFunction,
+{
+    // Windage due to gyroscopic drift, included in windage
+    pub fn spin_drift(&self) -> Length {
+        if self.simulation.flags.spin_drift {
+            self.simulation.spin_drift(self.time)
+        } else {
+            Length::ZERO
+        }
+    }
+
+    // Windage due to earth's rotation, included in windage
+    pub fn coriolis_drift(&self) -> Length {
+        self.simulation.line_of_sight(self.coriolis).get_z()
+    }
+
+    // Elevation due to earth's rotation (Eotvos effect), included in elevation
+    pub fn eotvos_shift(&self) -> Length {
+        self.simulation.line_of_sight(self.coriolis).get_y()
+    }
+
+    // Elevation due to aerodynamic jump, included in elevation
+    pub fn aerodynamic_jump(&self) -> Length {
+        if self.simulation.flags.aerodynamic_jump {
+            self.distance() * self.simulation.aerodynamic_jump().tan()
+        } else {
+            Length::ZERO
+        }
+    }
+}
+
 impl<D> Measurements for Packet<'_, D>
 where
     D: DragFunction,
@@ -57,17 +92,15 @@ where
     // This function returns the position rotated back to the initial frame of reference
     // This is used during zero'ing and is output in the drop table
     fn position(&self) -> MyVector3<length::Dimension> {
-        let position = self.simulation.position() + self.position;
-        position
-            .pivot_y(-self.simulation.shooter.yaw())
-            .pivot_z(-self.simulation.shooter.pitch())
-            .pivot_x(-self.simulation.shooter.roll())
+        self.simulation.relative_position(self.position)
     }
 
     fn mach(&self) -> Ratio {
-        let velocity = self.simulation.velocity() + self.velocity - self.simulation.wind_velocity();
+        let velocity = self.simulation.velocity() + self.velocity
+            - self.simulation.wind_velocity(self.position)
+            - self.gust;
         let velocity = velocity.norm();
-        self.simulation.mach(velocity)
+        velocity / self.simulation.local_sound_velocity(self.position)
     }
 
     fn energy(&self) -> Energy {
@@ -75,16 +108,17 @@ where
     }
 
     // Positions relative to line of sight (shooter_pitch)
+    // When earth centered, distance is along the line of sight curved with the surface instead
     fn distance(&self) -> Length {
-        self.position().get_x()
+        self.simulation.distance(self.position)
     }
 
     fn elevation(&self) -> Length {
-        self.position().get_y()
+        self.position().get_y() + self.aerodynamic_jump()
     }
 
     fn windage(&self) -> Length {
-        self.position().get_z()
+        self.position().get_z() + self.spin_drift()
     }
 
     fn angle(&self) -> Angle {
@@ -125,14 +159,18 @@ where
     fn lerp(&self, other: &Self, x: Length) -> Self {
         let dp = other.position - self.position;
         let dv = other.velocity - self.velocity;
+        let dg = other.gust - self.gust;
+        let dc = other.coriolis - self.coriolis;
         let da = other.acceleration - self.acceleration;
         let dt = other.time - self.time;
 
         let dx = x - self.distance();
-        let slope = dx / dp.get_x();
+        let slope = dx / (other.distance() - self.distance());
 
         let position = self.position + dp * slope;
         let velocity = self.velocity + dv * slope;
+        let gust = self.gust + dg * slope;
+        let coriolis = self.coriolis + dc * slope;
         let acceleration = self.acceleration + da * slope;
         let time = self.time + dt * slope;
 
@@ -141,6 +179,8 @@ where
             time,
             position,
             velocity,
+            gust,
+            coriolis,
             acceleration,
         }
     }
@@ -1,14 +1,37 @@
 use crate::{
     Numeric,
-    consts::PI,
-    simulation::{Atmosphere, Projectile, Scope, Shooter, Simulation, Wind},
+    consts::{FRAC_PI_8, PI},
+    drag::lerp,
+    error::Result,
+    random::Rng,
+    simulation::{
+        Atmosphere, Barrel, BarrelAdjustment, Frame, GravityModel, Model, Powder,
+        PowderSensitivity, Projectile, Scope, Shooter, Simulation, TwistDirection, Wind, WindFrame,
+        WindGradient, WindProfile,
+    },
     units::{
-        Acceleration, Angle, AngularVelocity, Area, ArealMassDensity, ConstZero, Length, Mass,
-        MassDensity, MolarHeatCapacity, MolarMass, Pressure, Ratio, ReciprocalLength, Velocity,
-        acceleration, angular_velocity, area::square_inch, length, mass::pound, my_quantity,
-        thermodynamic_temperature::degree_celsius, typenum::P2, velocity,
+        Acceleration, Angle, AngularVelocity, Area, ArealMassDensity, ConstZero, Frequency, Length,
+        Mass, MassDensity, MolarHeatCapacity, MolarMass, Pressure, Ratio, ReciprocalLength,
+        ThermodynamicTemperature, Time, Velocity, acceleration,
+        angle::{minute, radian},
+        angular_velocity,
+        angular_velocity::radian_per_second,
+        area::square_inch,
+        frequency,
+        frequency::hertz,
+        length,
+        length::{inch, meter},
+        mass::{grain, pound},
+        my_quantity,
+        pressure::pascal,
+        ratio, temperature_interval,
+        thermodynamic_temperature::{degree_celsius, kelvin},
+        time::second,
+        typenum::{P2, P3, P4},
+        velocity,
+        velocity::{foot_per_second, mile_per_hour},
     },
-    vectors::{Cross, MyVector3, Norm},
+    vectors::{Cross, Dot, MyVector3, Norm},
 };
 
 pub trait DragFunction {
@@ -16,7 +39,7 @@ pub trait DragFunction {
 }
 
 pub trait DragInit: Sized {
-    fn new(simulation: &Simulation<Self>) -> Self;
+    fn new(simulation: &Simulation<Self>) -> Result<Self>;
 }
 
 // Drag
@@ -58,15 +81,28 @@ where
 
     // FAST: a = V * v * cd'(v)
     // SLOW: a = V * v * cd(v) * rho * area * i * 1/m * -0.5
+    // With lapse enabled, the table (prescaled at the station's density and speed of sound) is
+    // looked up at the local mach, and the result is rescaled by the local density
+    // With bc bands, the table (prescaled by bc) is rescaled by bc over the band's bc
     pub(crate) fn drag_acceleration(
         &self,
+        position: MyVector3<length::Dimension>,
         velocity: MyVector3<velocity::Dimension>,
+        gust: MyVector3<velocity::Dimension>,
     ) -> MyVector3<acceleration::Dimension> {
         if self.flags.drag {
-            let velocity = velocity - self.wind_velocity();
+            let velocity = velocity - self.wind_velocity(position) - gust;
             let norm = velocity.norm();
-            let cd = self.drag.as_ref().unwrap().cd(norm);
-            velocity * norm * cd
+            let drag = self.drag.as_ref().unwrap();
+            let cd = if self.flags.lapse {
+                let height = self.height(position);
+                let mach = self.sound_velocity() / self.local_sound_velocity(position);
+                let rho = self.atmosphere.rho_at(height) / self.atmosphere.rho();
+                drag.cd(norm * mach) * rho
+            } else {
+                drag.cd(norm)
+            };
+            velocity * norm * cd * self.projectile.bc_scale(norm)
         } else {
             MyVector3::ZERO
         }
@@ -74,11 +110,227 @@ where
 
     pub(crate) fn acceleration(
         &self,
+        position: MyVector3<length::Dimension>,
+        velocity: MyVector3<velocity::Dimension>,
+        gust: MyVector3<velocity::Dimension>,
+        attitude: &Attitude,
+    ) -> MyVector3<acceleration::Dimension> {
+        let acceleration = self.coriolis_acceleration(position, velocity)
+            + self.drag_acceleration(position, velocity, gust)
+            + self.gravity_acceleration(position);
+        match self.model {
+            Model::PointMass => acceleration,
+            Model::ModifiedPointMass => {
+                let alpha = self.repose(position, velocity, gust, attitude.spin);
+                acceleration + self.yaw_acceleration(position, velocity, gust, attitude.spin, alpha)
+            }
+            Model::RigidBody => {
+                let alpha = self.yaw(position, velocity, gust, attitude.axis);
+                acceleration + self.yaw_acceleration(position, velocity, gust, attitude.spin, alpha)
+            }
+        }
+    }
+}
+
+// Rotational state of the projectile
+// Spin is tracked by the modified point mass and rigid body models, the axis of symmetry and its
+// transverse angular velocity by the rigid body model only
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Attitude {
+    pub(crate) spin: AngularVelocity,
+    pub(crate) axis: MyVector3<ratio::Dimension>,
+    pub(crate) rate: MyVector3<frequency::Dimension>,
+}
+
+// Modified point mass and rigid body
+impl<D> Simulation<D> {
+    // Projectile leaves the bore spinning, with its axis along the bore and no transverse rotation
+    pub(crate) fn initial_attitude(&self) -> Attitude {
+        let velocity = self.velocity();
+        Attitude {
+            spin: self.initial_spin(),
+            axis: velocity / velocity.norm(),
+            rate: MyVector3::ZERO,
+        }
+    }
+
+    // Spin rate at the muzzle, signed by twist direction (positive about the direction of travel)
+    // Derived from twist and muzzle velocity if not given
+    pub(crate) fn initial_spin(&self) -> AngularVelocity {
+        let spin = if self.projectile.spin > AngularVelocity::ZERO {
+            self.projectile.spin
+        } else {
+            let turns = (self.muzzle_velocity() / self.projectile.twist).get::<hertz>();
+            AngularVelocity::new::<radian_per_second>(2.0 * PI * turns)
+        };
+        spin * self.projectile.handedness()
+    }
+
+    // Yaw of repose, the equilibrium yaw of a spinning projectile as its trajectory turns
+    // alpha = -8 * Ix * p * (u x du/dt) / (pi * rho * d^3 * CMa * |u|^4)
+    // Only gravity and coriolis turn the trajectory, drag acts along it and drops out of the cross product
+    // Points right of the trajectory for right hand twist, causing spin drift
+    pub(crate) fn repose(
+        &self,
+        position: MyVector3<length::Dimension>,
+        velocity: MyVector3<velocity::Dimension>,
+        gust: MyVector3<velocity::Dimension>,
+        spin: AngularVelocity,
+    ) -> MyVector3<ratio::Dimension> {
+        let Projectile {
+            caliber,
+            axial_inertia,
+            coefficients,
+            ..
+        } = self.projectile;
+        if coefficients.overturning == 0.0 {
+            return MyVector3::ZERO;
+        }
+        let airspeed = velocity - self.wind_velocity(position) - gust;
+        let turn =
+            self.coriolis_acceleration(position, velocity) + self.gravity_acceleration(position);
+        let spin: Frequency = spin.into();
+        let norm = airspeed.norm();
+        let factor = axial_inertia * spin * -8.0
+            / (self.local_rho(position)
+                * caliber.powi(P3::new())
+                * norm.powi(P4::new())
+                * (PI * coefficients.overturning));
+        airspeed.cross(&turn) * factor
+    }
+
+    // Yaw of the axis of symmetry from the airspeed, as the component of the axis normal to it
+    pub(crate) fn yaw(
+        &self,
+        position: MyVector3<length::Dimension>,
+        velocity: MyVector3<velocity::Dimension>,
+        gust: MyVector3<velocity::Dimension>,
+        axis: MyVector3<ratio::Dimension>,
+    ) -> MyVector3<ratio::Dimension> {
+        let airspeed = velocity - self.wind_velocity(position) - gust;
+        let direction = airspeed / airspeed.norm();
+        axis - direction * axis.dot(&direction)
+    }
+
+    // Lift and magnus forces acting through yaw, and the increase in drag due to yaw
+    // lift:   pi * rho * d^2 * CLa * |u|^2 * alpha / 8m
+    // magnus: pi * rho * d^3 * p * CNpa * (alpha x u) / 8m
+    // drag:   -pi * rho * d^2 * CDa2 * |alpha|^2 * |u| * u / 8m
+    pub(crate) fn yaw_acceleration(
+        &self,
+        position: MyVector3<length::Dimension>,
         velocity: MyVector3<velocity::Dimension>,
+        gust: MyVector3<velocity::Dimension>,
+        spin: AngularVelocity,
+        alpha: MyVector3<ratio::Dimension>,
     ) -> MyVector3<acceleration::Dimension> {
-        self.coriolis_acceleration(velocity)
-            + self.drag_acceleration(velocity)
-            + self.gravity_acceleration()
+        let Projectile {
+            caliber,
+            weight,
+            coefficients,
+            ..
+        } = self.projectile;
+        let airspeed = velocity - self.wind_velocity(position) - gust;
+        let norm = airspeed.norm();
+        let scale = self.local_rho(position) * caliber.powi(P2::new()) * FRAC_PI_8 / weight;
+        let spin: Frequency = spin.into();
+        let yaw = alpha.norm().get::<ratio::ratio>();
+
+        let lift = alpha * (scale * norm.powi(P2::new()) * coefficients.lift);
+        let magnus = alpha.cross(&airspeed) * (scale * caliber * spin * coefficients.magnus);
+        let drag = airspeed * (scale * norm * -(coefficients.yaw_drag * yaw * yaw));
+        lift + magnus + drag
+    }
+
+    // Rotational state after one time step
+    pub(crate) fn rotate(
+        &self,
+        position: MyVector3<length::Dimension>,
+        velocity: MyVector3<velocity::Dimension>,
+        gust: MyVector3<velocity::Dimension>,
+        attitude: &Attitude,
+    ) -> Attitude {
+        match self.model {
+            Model::PointMass => *attitude,
+            Model::ModifiedPointMass => Attitude {
+                spin: attitude.spin + self.spin_decay(position, velocity, gust, attitude.spin),
+                ..*attitude
+            },
+            Model::RigidBody => self.rigid_body_rotate(position, velocity, gust, attitude),
+        }
+    }
+
+    // Change in spin rate over one time step, damped by the spin damping moment
+    // dp/dt = pi * rho * d^4 * |u| * Clp * p / 8Ix
+    fn spin_decay(
+        &self,
+        position: MyVector3<length::Dimension>,
+        velocity: MyVector3<velocity::Dimension>,
+        gust: MyVector3<velocity::Dimension>,
+        spin: AngularVelocity,
+    ) -> AngularVelocity {
+        let Projectile {
+            caliber,
+            axial_inertia,
+            coefficients,
+            ..
+        } = self.projectile;
+        let airspeed = velocity - self.wind_velocity(position) - gust;
+        let rate = self.local_rho(position)
+            * caliber.powi(P4::new())
+            * airspeed.norm()
+            * (FRAC_PI_8 * coefficients.spin_damping)
+            / axial_inertia;
+        spin * (rate * self.time_step).get::<ratio::ratio>()
+    }
+
+    // Axisymmetric rigid body, angular momentum H = Ix * p * x + Iy * w, with w the transverse rate
+    // Moments, with k = pi * rho * d^3 / 8:
+    // overturning:   k * |u| * CMa * (u x x)
+    // pitch damping: k * d * |u| * CMq * w
+    // magnus:        k * d * p * CMpa * (alpha x u)
+    // These are normal to the axis, so only spin damping changes spin
+    // dw/dt = (M - Ix * p * (w x x)) / Iy, keeping w normal to the axis
+    // dx/dt = w x x
+    fn rigid_body_rotate(
+        &self,
+        position: MyVector3<length::Dimension>,
+        velocity: MyVector3<velocity::Dimension>,
+        gust: MyVector3<velocity::Dimension>,
+        attitude: &Attitude,
+    ) -> Attitude {
+        let Projectile {
+            caliber,
+            axial_inertia,
+            transverse_inertia,
+            coefficients,
+            ..
+        } = self.projectile;
+        let Attitude { spin, axis, rate } = *attitude;
+        let airspeed = velocity - self.wind_velocity(position) - gust;
+        let norm = airspeed.norm();
+        let alpha = self.yaw(position, velocity, gust, axis);
+        let k = self.local_rho(position) * caliber.powi(P3::new()) * FRAC_PI_8;
+        let p: Frequency = spin.into();
+
+        let overturning = airspeed.cross(&axis) * (k * norm * coefficients.overturning);
+        let damping = rate * (k * caliber * norm * coefficients.pitch_damping);
+        let magnus = alpha.cross(&airspeed) * (k * caliber * p * coefficients.magnus_moment);
+        let moment = overturning + damping + magnus;
+
+        // The gyroscopic term only precesses the rate about the axis, at Ix * p / Iy, so it's
+        // applied as an exact rotation; an explicit step grows the fast mode without bound
+        let precession =
+            (axial_inertia * p / transverse_inertia * self.time_step).get::<ratio::ratio>();
+        let rate = rate * precession.cos() + axis.cross(&rate) * precession.sin();
+        let rate = rate + moment / transverse_inertia * self.time_step;
+        let axis = axis + rate.cross(&axis) * self.time_step;
+        let axis = axis / axis.norm();
+        Attitude {
+            spin: spin + self.spin_decay(position, velocity, gust, spin),
+            axis,
+            rate: rate - axis * rate.dot(&axis),
+        }
     }
 }
 
@@ -91,22 +343,142 @@ impl<D> Simulation<D> {
         velocity / self.sound_velocity()
     }
 
-    // Velocity vector of wind, only horizontal at the moment
-    // Does not adjust according to line of sight, since most would measure wind
-    // along relative bearing - I don't think many would factor in a 'downhill' wind for example
-    // This would be interresting to think of, however.
-    pub(crate) fn wind_velocity(&self) -> MyVector3<velocity::Dimension> {
-        self.atmosphere
-            .wind
-            .velocity()
-            .pivot_x(self.shooter.roll())
-            .pivot_z(self.shooter.pitch())
-            .pivot_y(self.shooter.yaw())
+    // Density at the projectile's height above the station when lapse is enabled,
+    // otherwise the station's density
+    pub(crate) fn local_rho(&self, position: MyVector3<length::Dimension>) -> MassDensity {
+        if self.flags.lapse {
+            self.atmosphere.rho_at(self.height(position))
+        } else {
+            self.atmosphere.rho()
+        }
+    }
+
+    // Speed of sound at the projectile's height above the station when lapse is enabled,
+    // otherwise the station's speed of sound
+    pub(crate) fn local_sound_velocity(&self, position: MyVector3<length::Dimension>) -> Velocity {
+        if self.flags.lapse {
+            self.atmosphere.sound_velocity_at(self.height(position))
+        } else {
+            self.sound_velocity()
+        }
+    }
+
+    // Velocity vector of wind
+    // Taken from the zone covering the projectile's current distance downrange, and scaled
+    // by the wind gradient at its height above the shooter
+    // In the line of sight frame, wind is measured relative to the shooter's bearing and incline,
+    // so it is rotated along with the shot.  In the world frame, wind is measured relative to
+    // north and the horizon, which the simulation already uses, so it is left as is.
+    pub(crate) fn wind_velocity(
+        &self,
+        position: MyVector3<length::Dimension>,
+    ) -> MyVector3<velocity::Dimension> {
+        let wind = &self.atmosphere.wind;
+        let wind = if wind.zones.len() > 1 {
+            wind.zone(self.distance(position))
+        } else {
+            wind.last()
+        };
+        let velocity =
+            wind.velocity() * self.atmosphere.wind.gradient.factor(self.height(position));
+        match wind.frame {
+            WindFrame::LineOfSight => velocity
+                .pivot_x(self.shooter.roll())
+                .pivot_z(self.shooter.pitch())
+                .pivot_y(self.shooter.yaw()),
+            WindFrame::World => velocity,
+        }
+    }
+
+    // Gyroscopic stability factor of the configured load, at muzzle velocity in the station's atmosphere
+    pub fn stability(&self) -> Numeric {
+        self.projectile
+            .stability(self.muzzle_velocity(), self.atmosphere.rho())
+    }
+
+    // Gyroscopic (spin) drift after time of flight, Litz's empirical formula
+    // 1.25 * (Sg + 1.2) * t^1.83, in inches
+    pub(crate) fn spin_drift(&self, time: Time) -> Length {
+        let drift = 1.25 * (self.stability() + 1.2) * time.get::<second>().powf(1.83);
+        Length::new::<inch>(drift * self.projectile.handedness())
+    }
+
+    // Vertical deflection due to crosswind at the muzzle, Litz's empirical formula
+    // (0.01 * Sg - 0.0024 * length (calibers) + 0.032) MOA per mph of crosswind
+    // For right hand twist, wind blowing left to right jumps up, and right to left jumps down
+    pub fn aerodynamic_jump(&self) -> Angle {
+        let crosswind = self
+            .wind_velocity(MyVector3::ZERO)
+            .pivot_y(-self.shooter.yaw())
+            .pivot_z(-self.shooter.pitch())
+            .pivot_x(-self.shooter.roll())
+            .get_z();
+        let length = (self.projectile.length / self.projectile.caliber).get::<ratio::ratio>();
+        let factor = 0.01 * self.stability() - 0.0024 * length + 0.032;
+        Angle::new::<minute>(
+            factor * crosswind.get::<mile_per_hour>() * self.projectile.handedness(),
+        )
+    }
+
+    // Gust velocity at the muzzle, drawn from the process' stationary distribution
+    pub(crate) fn initial_gust(&self, rng: &mut Rng) -> MyVector3<velocity::Dimension> {
+        if self.atmosphere.wind.gust.intensity > Velocity::ZERO {
+            self.random_gust(rng)
+        } else {
+            MyVector3::ZERO
+        }
+    }
+
+    // Gust velocity one time step after previous, exact discretization of the Ornstein-Uhlenbeck process
+    pub(crate) fn gust_velocity(
+        &self,
+        previous: MyVector3<velocity::Dimension>,
+        rng: &mut Rng,
+    ) -> MyVector3<velocity::Dimension> {
+        let gust = &self.atmosphere.wind.gust;
+        if gust.intensity > Velocity::ZERO {
+            let decay = (-(self.time_step / gust.correlation).get::<ratio::ratio>()).exp();
+            previous * decay + self.random_gust(rng) * (1.0 - decay * decay).sqrt()
+        } else {
+            MyVector3::ZERO
+        }
+    }
+
+    fn random_gust(&self, rng: &mut Rng) -> MyVector3<velocity::Dimension> {
+        let intensity = self.atmosphere.wind.gust.intensity;
+        MyVector3::new(
+            intensity * rng.normal(),
+            intensity * rng.normal(),
+            intensity * rng.normal(),
+        )
     }
 
     // Projectiles initial velocity relative to scope
+    // Muzzle velocity after adjustments
+    pub fn muzzle_velocity(&self) -> Velocity {
+        self.projectile.velocity + self.powder_adjustment() + self.barrel_adjustment()
+    }
+
+    // Change in muzzle velocity due to barrel length
+    pub fn barrel_adjustment(&self) -> Velocity {
+        self.projectile.barrel.adjustment()
+    }
+
+    // Change in muzzle velocity due to powder temperature
+    pub fn powder_adjustment(&self) -> Velocity {
+        self.projectile.powder.adjustment(self.powder_temperature())
+    }
+
+    // Powder temperature, taken to be the air temperature unless given
+    pub fn powder_temperature(&self) -> ThermodynamicTemperature {
+        self.projectile
+            .powder
+            .temperature
+            .unwrap_or(self.atmosphere.temperature)
+    }
+
     pub(crate) fn velocity(&self) -> MyVector3<velocity::Dimension> {
-        MyVector3::new(self.projectile.velocity, Velocity::ZERO, Velocity::ZERO)
+        MyVector3::new(self.muzzle_velocity(), Velocity::ZERO, Velocity::ZERO)
             .pivot_y(self.scope.yaw())
             .pivot_z(self.scope.pitch())
             .pivot_x(self.shooter.roll())
@@ -114,6 +486,58 @@ impl<D> Simulation<D> {
             .pivot_y(self.shooter.yaw())
     }
 
+    // Position rotated back from the shooter's bearing and line of sight to the initial frame of reference
+    pub(crate) fn relative_position(
+        &self,
+        position: MyVector3<length::Dimension>,
+    ) -> MyVector3<length::Dimension> {
+        self.line_of_sight(self.position() + position)
+    }
+
+    // Vector rotated back from the shooter's bearing and line of sight
+    pub(crate) fn line_of_sight(
+        &self,
+        vector: MyVector3<length::Dimension>,
+    ) -> MyVector3<length::Dimension> {
+        vector
+            .pivot_y(-self.shooter.yaw())
+            .pivot_z(-self.shooter.pitch())
+            .pivot_x(-self.shooter.roll())
+    }
+
+    // Height above the shooter, or above the surface through the shooter when earth centered
+    pub(crate) fn height(&self, position: MyVector3<length::Dimension>) -> Length {
+        match self.frame {
+            Frame::Flat => position.get_y(),
+            Frame::EarthCentered => self.radius(position).norm() - self.shooter_radius(),
+        }
+    }
+
+    // Distance downrange, along the line of sight, or when earth centered along the line of sight
+    // curved with the surface at the shooter's altitude, so that elevation and windage stay
+    // measured in the frame distance is
+    pub(crate) fn distance(&self, position: MyVector3<length::Dimension>) -> Length {
+        match self.frame {
+            Frame::Flat => self.relative_position(position).get_x(),
+            Frame::EarthCentered => {
+                let position = self.relative_position(position);
+                let radius = self.shooter_radius();
+                let angle = position.get_x().atan2(radius + position.get_y());
+                radius * angle.get::<radian>()
+            }
+        }
+    }
+
+    // Distance from earth's center to the shooter
+    fn shooter_radius(&self) -> Length {
+        Shooter::EARTH_RADIUS + self.atmosphere.altitude
+    }
+
+    // Vector from earth's center to position, which is relative to the shooter
+    fn radius(&self, position: MyVector3<length::Dimension>) -> MyVector3<length::Dimension> {
+        position + MyVector3::new(Length::ZERO, self.shooter_radius(), Length::ZERO)
+    }
+
     // Projectiles initial position relative to scope
     pub(crate) fn position(&self) -> MyVector3<length::Dimension> {
         MyVector3::new(Length::ZERO, -self.scope.height, -self.scope.offset)
@@ -130,20 +554,43 @@ impl<D> Simulation<D> {
     // Also accounts for elevation changes when launching projectils East/West, regardless of hemisphere
     // Bearing East results in higher elevation (+y absolute/relative)
     // Bearing West results in lower elevation (-y relative/absolute)
+    // When earth centered, also the change in centrifugal acceleration from the shooter's, which
+    // is already part of gravity there: -w x (w x r) + w x (w x r0) = -w x (w x (r - r0))
     pub(crate) fn coriolis_acceleration(
         &self,
+        position: MyVector3<length::Dimension>,
         velocity: MyVector3<velocity::Dimension>,
     ) -> MyVector3<acceleration::Dimension> {
         if self.flags.coriolis {
-            self.shooter.omega().cross(&velocity) * -2.0
+            let omega = self.shooter.omega();
+            let coriolis = omega.cross(&velocity) * -2.0;
+            match self.frame {
+                Frame::Flat => coriolis,
+                Frame::EarthCentered => coriolis - omega.cross(&omega.cross(&position)),
+            }
         } else {
             MyVector3::ZERO
         }
     }
 
-    pub(crate) fn gravity_acceleration(&self) -> MyVector3<acceleration::Dimension> {
+    // When earth centered, gravity at the shooter points to earth's center and falls off with
+    // the square of the distance from it
+    pub(crate) fn gravity_acceleration(
+        &self,
+        position: MyVector3<length::Dimension>,
+    ) -> MyVector3<acceleration::Dimension> {
         if self.flags.gravity {
-            self.shooter.gravity()
+            let gravity = self.shooter.gravity(self.atmosphere.altitude);
+            match self.frame {
+                Frame::Flat => gravity,
+                Frame::EarthCentered => {
+                    let radius = self.radius(position);
+                    let norm = radius.norm();
+                    radius
+                        * (gravity.get_y() * self.shooter_radius().powi(P2::new())
+                            / norm.powi(P3::new()))
+                }
+            }
         } else {
             MyVector3::ZERO
         }
@@ -163,36 +610,199 @@ impl Atmosphere {
     // Adiabatic index of air, mostly diatomic gas
     const ADIABATIC_INDEX_AIR: Numeric = 1.4;
 
+    // Effective earth radius used to convert geometric to geopotential altitude (m)
+    const EARTH_RADIUS_GEOPOTENTIAL: Numeric = 6_356_766.0;
+
+    // Hydrostatic constant g0 * M / R* of the 1976 standard, (K/m)
+    const HYDROSTATIC_CONSTANT: Numeric = 0.034_163_195;
+
+    // Range of altitudes covered by the standard layers below
+    pub(crate) const STANDARD_ALTITUDE_MIN: Length = my_quantity!(-2_000.0);
+    pub(crate) const STANDARD_ALTITUDE_MAX: Length = my_quantity!(51_000.0);
+
+    // Layers of the U.S. Standard Atmosphere (1976), identical to ICAO below 32km
+    // (base geopotential altitude (m), base temperature (K), base pressure (Pa), lapse rate (K/m))
+    const STANDARD_LAYERS: [(Numeric, Numeric, Numeric, Numeric); 5] = [
+        (0.0, 288.15, 101_325.0, -0.0065),
+        (11_000.0, 216.65, 22_632.06, 0.0),
+        (20_000.0, 216.65, 5_474.889, 0.001),
+        (32_000.0, 228.65, 868.018_7, 0.0028),
+        (47_000.0, 270.65, 110.906_3, 0.0),
+    ];
+
+    // Temperature of the standard atmosphere at given geometric altitude
+    pub fn standard_temperature(altitude: Length) -> ThermodynamicTemperature {
+        let (temperature, _) = Self::standard(altitude);
+        ThermodynamicTemperature::new::<kelvin>(temperature)
+    }
+
+    // Pressure of the standard atmosphere at given geometric altitude
+    pub fn standard_pressure(altitude: Length) -> Pressure {
+        let (_, pressure) = Self::standard(altitude);
+        Pressure::new::<pascal>(pressure)
+    }
+
+    // Temperature (K) and pressure (Pa) within the layer containing the geopotential altitude
+    // Altitudes below sea level extrapolate the first layer, those above the last layer extend it
+    fn standard(altitude: Length) -> (Numeric, Numeric) {
+        let z = altitude.get::<meter>();
+        let h = Self::EARTH_RADIUS_GEOPOTENTIAL * z / (Self::EARTH_RADIUS_GEOPOTENTIAL + z);
+        let &(base, temperature, pressure, lapse) = Self::STANDARD_LAYERS
+            .iter()
+            .rev()
+            .find(|&&(base, ..)| h >= base)
+            .unwrap_or(&Self::STANDARD_LAYERS[0]);
+        let dh = h - base;
+        if lapse == 0.0 {
+            (
+                temperature,
+                pressure * (-Self::HYDROSTATIC_CONSTANT * dh / temperature).exp(),
+            )
+        } else {
+            let t = temperature + lapse * dh;
+            (
+                t,
+                pressure * (temperature / t).powf(Self::HYDROSTATIC_CONSTANT / lapse),
+            )
+        }
+    }
+
     // Density of air, using pressure, humidity, and temperature
     pub fn rho(&self) -> MassDensity {
-        ((self.pd() * Self::MOLAR_MASS_DRY_AIR) + (self.pv() * Self::MOLAR_MASS_WATER_VAPOR))
-            / (Self::MOLAR_GAS_UNIVERSAL * self.temperature)
+        self.density(self.temperature, self.pressure)
     }
 
     // Speed of sound at given air density and pressure
     pub fn sound_velocity(&self) -> Velocity {
-        (Self::ADIABATIC_INDEX_AIR * (self.pressure / self.rho())).sqrt()
+        self.speed_of_sound(self.temperature, self.pressure)
     }
 
-    // Pressure of water vapor, Arden Buck equation
-    fn pv(&self) -> Pressure {
-        my_quantity!(
-            self.humidity
-                * 611.21
-                * ((18.678 - (self.celsius() / 234.5))
-                    * (self.celsius() / (257.14 + self.celsius())))
-                .exp()
-        )
+    // Density of air at height above the station
+    pub fn rho_at(&self, height: Length) -> MassDensity {
+        let (temperature, pressure) = self.lapse(height);
+        self.density(temperature, pressure)
+    }
+
+    // Speed of sound at height above the station
+    pub fn sound_velocity_at(&self, height: Length) -> Velocity {
+        let (temperature, pressure) = self.lapse(height);
+        self.speed_of_sound(temperature, pressure)
+    }
+
+    // Pressure as measured at the station, uncorrected for altitude
+    pub fn station_pressure(&self) -> Pressure {
+        self.pressure
+    }
+
+    // Pressure corrected to sea level, as given in weather reports
+    pub fn barometric_pressure(&self) -> Pressure {
+        self.pressure * Self::barometric_ratio(self.altitude)
+    }
+
+    // Altitude in the standard atmosphere with the same density as this atmosphere
+    // Standard density decreases with altitude, so bisect across the range of modelled layers
+    pub fn density_altitude(&self) -> Length {
+        let rho = self.rho();
+        let mut min = Self::STANDARD_ALTITUDE_MIN;
+        let mut max = Self::STANDARD_ALTITUDE_MAX;
+        for _ in 0..64 {
+            let mid = (min + max) / 2.0;
+            if Self::standard_rho(mid) > rho {
+                min = mid;
+            } else {
+                max = mid;
+            }
+        }
+        (min + max) / 2.0
+    }
+
+    // Ratio of sea level to station pressure in the standard atmosphere
+    pub(crate) fn barometric_ratio(altitude: Length) -> Numeric {
+        let (_, p0) = Self::standard(Length::ZERO);
+        let (_, p1) = Self::standard(altitude);
+        p0 / p1
+    }
+
+    // Density of dry air in the standard atmosphere
+    pub(crate) fn standard_rho(altitude: Length) -> MassDensity {
+        Self::standard_pressure(altitude) * Self::MOLAR_MASS_DRY_AIR
+            / (Self::MOLAR_GAS_UNIVERSAL * Self::standard_temperature(altitude))
+    }
+
+    // Pressure of dry air at temperature with density rho
+    pub(crate) fn dry_pressure(
+        temperature: ThermodynamicTemperature,
+        rho: MassDensity,
+    ) -> Pressure {
+        rho * Self::MOLAR_GAS_UNIVERSAL * temperature / Self::MOLAR_MASS_DRY_AIR
+    }
+
+    // Temperature and pressure at height above the station
+    // Follows the standard atmosphere's change from the station's altitude, keeping the station's
+    // deviation from standard temperature, and its ratio to standard pressure
+    fn lapse(&self, height: Length) -> (ThermodynamicTemperature, Pressure) {
+        let (t0, p0) = Self::standard(self.altitude);
+        let (t1, p1) = Self::standard(self.altitude + height);
+        let temperature =
+            ThermodynamicTemperature::new::<kelvin>(self.temperature.get::<kelvin>() + (t1 - t0));
+        let pressure = self.pressure * (p1 / p0);
+        (temperature, pressure)
+    }
+
+    fn density(&self, temperature: ThermodynamicTemperature, pressure: Pressure) -> MassDensity {
+        let pv = self.pv(temperature);
+        let pd = pressure - pv;
+        ((pd * Self::MOLAR_MASS_DRY_AIR) + (pv * Self::MOLAR_MASS_WATER_VAPOR))
+            / (Self::MOLAR_GAS_UNIVERSAL * temperature)
+    }
+
+    fn speed_of_sound(
+        &self,
+        temperature: ThermodynamicTemperature,
+        pressure: Pressure,
+    ) -> Velocity {
+        (Self::ADIABATIC_INDEX_AIR * (pressure / self.density(temperature, pressure))).sqrt()
+    }
+
+    // Pressure of water vapor
+    fn pv(&self, temperature: ThermodynamicTemperature) -> Pressure {
+        Self::saturation_pressure(temperature) * self.humidity
+    }
+
+    // Saturation pressure of water vapor, Arden Buck equation
+    pub(crate) fn saturation_pressure(temperature: ThermodynamicTemperature) -> Pressure {
+        let celsius = temperature.get::<degree_celsius>();
+        my_quantity!(611.21 * ((18.678 - (celsius / 234.5)) * (celsius / (257.14 + celsius))).exp())
     }
 
-    // Pressure of dry air
-    fn pd(&self) -> Pressure {
-        self.pressure - self.pv()
+    // Vapor pressure from wet-bulb temperature, using the psychrometer coefficient of a
+    // ventilated (Assmann) psychrometer
+    pub(crate) fn psychrometric_pressure(
+        temperature: ThermodynamicTemperature,
+        wet_bulb: ThermodynamicTemperature,
+        pressure: Pressure,
+    ) -> Pressure {
+        let wet = wet_bulb.get::<degree_celsius>();
+        let depression = temperature.get::<degree_celsius>() - wet;
+        let coefficient = 0.000_66 * (1.0 + 0.001_15 * wet);
+        Self::saturation_pressure(wet_bulb) - pressure * coefficient * depression
     }
 
-    // Temperature in celsius
-    fn celsius(&self) -> Numeric {
-        self.temperature.get::<degree_celsius>()
+    pub fn relative_humidity(&self) -> Numeric {
+        self.humidity
+    }
+
+    // Temperature at which the current vapor pressure saturates, Arden Buck equation solved for temperature
+    // ln(pv/611.21) = (18.678 - T/234.5) * (T/(257.14 + T)) is a quadratic in T, the lower root is taken
+    // None for perfectly dry air, which has no dew point
+    pub fn dew_point(&self) -> Option<ThermodynamicTemperature> {
+        if self.humidity <= 0.0 {
+            return None;
+        }
+        let y = (self.pv(self.temperature).get::<pascal>() / 611.21).ln();
+        let b = 18.678 - y;
+        let celsius = 234.5 / 2.0 * (b - (b * b - 4.0 * 257.14 * y / 234.5).sqrt());
+        Some(ThermodynamicTemperature::new::<degree_celsius>(celsius))
     }
 }
 
@@ -214,11 +824,54 @@ impl Shooter {
     // Angular velocity of earth, (rad/s)
     const ANGULAR_VELOCITY: AngularVelocity = my_quantity!(0.000_072_921_159);
 
+    // Mean radius of earth (m)
+    const EARTH_RADIUS: Length = my_quantity!(6_371_008.8);
+
     // Gravity of earth (m/s^2)
     const GRAVITY: Acceleration = my_quantity!(-9.806_65);
 
-    pub fn gravity(&self) -> MyVector3<acceleration::Dimension> {
-        MyVector3::new(Acceleration::ZERO, Self::GRAVITY, Acceleration::ZERO)
+    // WGS-84 normal gravity at the equator (m/s^2)
+    const EQUATORIAL_GRAVITY: Acceleration = my_quantity!(9.780_325_335_9);
+
+    // WGS-84 semi-major axis (m)
+    const SEMI_MAJOR_AXIS: Length = my_quantity!(6_378_137.0);
+
+    // WGS-84 flattening
+    const FLATTENING: Numeric = 1.0 / 298.257_223_563;
+
+    // WGS-84 Somigliana constant, (b * gp) / (a * ge) - 1
+    const SOMIGLIANA: Numeric = 0.001_931_852_652_41;
+
+    // WGS-84 first eccentricity squared
+    const ECCENTRICITY_SQUARED: Numeric = 0.006_694_379_990_14;
+
+    // WGS-84 ratio of centrifugal to gravitational acceleration at the equator, w^2 * a^2 * b / GM
+    const GRAVITY_RATIO: Numeric = 0.003_449_786_506_84;
+
+    // Gravity vector at the shooter, at altitude above the ellipsoid
+    pub fn gravity(&self, altitude: Length) -> MyVector3<acceleration::Dimension> {
+        let g = match self.gravity {
+            GravityModel::Standard => Self::GRAVITY,
+            GravityModel::Normal => -self.normal_gravity(altitude),
+        };
+        MyVector3::new(Acceleration::ZERO, g, Acceleration::ZERO)
+    }
+
+    // Somigliana's closed form for gravity on the ellipsoid
+    // ge * (1 + k * sin^2(lat)) / sqrt(1 - e^2 * sin^2(lat))
+    // with the second order free-air reduction for height h above it
+    // 1 - 2h/a * (1 + f + m - 2f * sin^2(lat)) + 3h^2/a^2
+    fn normal_gravity(&self, altitude: Length) -> Acceleration {
+        let sin_sq = self.latitude.sin().get::<ratio::ratio>().powi(2);
+        let surface = Self::EQUATORIAL_GRAVITY * (1.0 + Self::SOMIGLIANA * sin_sq)
+            / (1.0 - Self::ECCENTRICITY_SQUARED * sin_sq).sqrt();
+        let h = (altitude / Self::SEMI_MAJOR_AXIS).get::<ratio::ratio>();
+        let reduction = 1.0
+            - 2.0
+                * h
+                * (1.0 + Self::FLATTENING + Self::GRAVITY_RATIO - 2.0 * Self::FLATTENING * sin_sq)
+            + 3.0 * h.powi(2);
+        surface * reduction
     }
 
     pub(crate) fn yaw(&self) -> Angle {
@@ -246,6 +899,39 @@ impl Shooter {
     }
 }
 
+impl WindProfile {
+    // Zone with the furthest start not beyond distance, with the first zone covering the muzzle
+    fn zone(&self, distance: Length) -> &Wind {
+        let i = self.zones.partition_point(|wind| wind.range <= distance);
+        &self.zones[i.saturating_sub(1)]
+    }
+}
+
+impl WindGradient {
+    // Ratio of wind speed at height above the shooter to the measured wind speed
+    // Below the shooter, height above ground is unknown, so the measured speed is used
+    fn factor(&self, height: Length) -> Numeric {
+        match *self {
+            Self::Uniform => 1.0,
+            Self::PowerLaw {
+                exponent,
+                height: reference,
+            } => {
+                let scale = (reference + height.max(Length::ZERO)) / reference;
+                scale.get::<ratio::ratio>().powf(exponent)
+            }
+            Self::Logarithmic {
+                roughness,
+                height: reference,
+            } => {
+                let numerator = (reference + height.max(Length::ZERO)) / roughness;
+                let denominator = reference / roughness;
+                numerator.get::<ratio::ratio>().ln() / denominator.get::<ratio::ratio>().ln()
+            }
+        }
+    }
+}
+
 impl Wind {
     // This vector indicates direction of wind flow, not source of wind
     fn yaw(&self) -> Angle {
@@ -256,10 +942,50 @@ impl Wind {
         self.pitch
     }
 
+    // Pitch is applied first, so that it remains an elevation angle regardless of yaw
     fn velocity(&self) -> MyVector3<velocity::Dimension> {
         MyVector3::new(self.velocity, Velocity::ZERO, Velocity::ZERO)
-            .pivot_y(self.yaw())
             .pivot_z(self.pitch())
+            .pivot_y(self.yaw())
+    }
+}
+
+impl Powder {
+    fn adjustment(&self, temperature: ThermodynamicTemperature) -> Velocity {
+        match &self.sensitivity {
+            PowderSensitivity::Constant => Velocity::ZERO,
+            PowderSensitivity::Linear { velocity, interval } => {
+                let change = temperature.get::<kelvin>() - self.reference.get::<kelvin>();
+                *velocity * (change / interval.get::<temperature_interval::kelvin>())
+            }
+            PowderSensitivity::Table(table) => {
+                // Temperatures don't subtract to their own type, so are interpolated in kelvin
+                let (xs, ys): (Vec<_>, Vec<_>) = table
+                    .iter()
+                    .map(|&(temperature, velocity)| (temperature.get::<kelvin>(), velocity))
+                    .unzip();
+                lerp(&xs, &ys, temperature.get::<kelvin>())
+                    - lerp(&xs, &ys, self.reference.get::<kelvin>())
+            }
+        }
+    }
+}
+
+impl Barrel {
+    fn adjustment(&self) -> Velocity {
+        let (Some(test), Some(actual)) = (self.test, self.actual) else {
+            return Velocity::ZERO;
+        };
+        match &self.adjustment {
+            BarrelAdjustment::Constant => Velocity::ZERO,
+            BarrelAdjustment::Linear { velocity, length } => {
+                *velocity * ((actual - test) / *length).get::<ratio::ratio>()
+            }
+            BarrelAdjustment::Table(table) => {
+                let (xs, ys): (Vec<_>, Vec<_>) = table.iter().copied().unzip();
+                lerp(&xs, &ys, actual) - lerp(&xs, &ys, test)
+            }
+        }
     }
 }
 
@@ -277,12 +1003,53 @@ impl Projectile {
     }
 
     pub fn bc(&self) -> ArealMassDensity {
-        let mass = Mass::new::<pound>(self.bc);
+        let mass = Mass::new::<pound>(self.reference_bc());
         let area = Area::new::<square_inch>(1.0);
         mass / area
     }
 
+    // Ballistic coefficient drag tables are prescaled by, the lowest band's if bc isn't given
+    pub(crate) fn reference_bc(&self) -> Numeric {
+        match self.bc_bands.first() {
+            Some(&(_, bc)) if self.bc == 0.0 => bc,
+            _ => self.bc,
+        }
+    }
+
+    // Ratio of bc to the bc of the band covering velocity, 1.0 without bands
+    pub(crate) fn bc_scale(&self, velocity: Velocity) -> Numeric {
+        let i = self
+            .bc_bands
+            .partition_point(|&(threshold, _)| threshold <= velocity);
+        match self.bc_bands.get(i.saturating_sub(1)) {
+            Some(&(_, bc)) => self.reference_bc() / bc,
+            None => 1.0,
+        }
+    }
+
     pub fn sd(&self) -> ArealMassDensity {
         self.weight / self.caliber.powi(P2::new())
     }
+
+    // Gyroscopic stability factor, Miller twist rule
+    // Corrected for velocity from 2800fps, and for density from the standard sea level atmosphere
+    pub fn stability(&self, velocity: Velocity, rho: MassDensity) -> Numeric {
+        let mass = self.weight.get::<grain>();
+        let caliber = self.caliber.get::<inch>();
+        let twist = (self.twist / self.caliber).get::<ratio::ratio>();
+        let length = (self.length / self.caliber).get::<ratio::ratio>();
+        let velocity = (velocity.get::<foot_per_second>() / 2800.0).cbrt();
+        let density = (Atmosphere::standard_rho(Length::ZERO) / rho).get::<ratio::ratio>();
+        30.0 * mass / (twist.powi(2) * caliber.powi(3) * length * (1.0 + length.powi(2)))
+            * velocity
+            * density
+    }
+
+    // Sign of lateral effects of spin, positive (right) for right hand twist
+    pub(crate) fn handedness(&self) -> Numeric {
+        match self.twist_direction {
+            TwistDirection::Right => 1.0,
+            TwistDirection::Left => -1.0,
+        }
+    }
 }
@@ -0,0 +1,171 @@
+use crate::{
+    Numeric,
+    drag::custom,
+    error::{Error, Result},
+    simulation::Simulation,
+    units::{Time, Velocity, length::meter, time::second, velocity::meter_per_second},
+};
+
+// Doppler radar sample, velocity along the line of sight at time after the shot
+#[derive(Debug, Clone, Copy)]
+pub struct Sample {
+    pub time: Time,
+    pub velocity: Velocity,
+}
+
+// Drag coefficient derived at one sample, with the difference between the measured and smoothed
+// velocity there
+#[derive(Debug, Clone, Copy)]
+pub struct Point {
+    pub mach: Numeric,
+    pub cd: Numeric,
+    pub residual: Velocity,
+}
+
+// Drag curve derived from a radar track, in the order sampled (descending mach)
+#[derive(Debug, Clone)]
+pub struct Analysis {
+    pub points: Vec<Point>,
+}
+
+// Samples from csv lines of time (s) and velocity (m/s)
+// Blank lines, lines starting with #, and a header that doesn't parse as the first other line are skipped
+pub fn parse(csv: &str) -> Result<Vec<Sample>> {
+    let mut samples = Vec::new();
+    let mut header = true;
+    for (i, line) in csv.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let first = std::mem::replace(&mut header, false);
+        let mut fields = line.split(',').map(|field| field.trim().parse::<Numeric>());
+        match (fields.next(), fields.next()) {
+            (Some(Ok(time)), Some(Ok(velocity))) => samples.push(Sample {
+                time: Time::new::<second>(time),
+                velocity: Velocity::new::<meter_per_second>(velocity),
+            }),
+            _ if first => {}
+            _ => return Err(Error::Parse { line: i + 1 }),
+        }
+    }
+    Ok(samples)
+}
+
+// Smooths samples with a quadratic fitted over a window of neighbouring samples, differentiating
+// the fit for deceleration, and derives cd from the simulation's atmosphere and projectile
+// a = -rho * cd * area * v^2 / 2m
+// The track is taken to be flat fire in still air, so neither gravity along the path nor wind
+// are removed from the deceleration
+pub fn analyze<D>(
+    simulation: &Simulation<D>,
+    samples: &[Sample],
+    window: usize,
+) -> Result<Analysis> {
+    if window < 3 || window.is_multiple_of(2) {
+        return Err(Error::NumericOutOfRange {
+            value: window as Numeric,
+            min: 3.0,
+            max: samples.len() as Numeric,
+        });
+    }
+    if samples.len() < window {
+        return Err(Error::TableTooShort {
+            length: samples.len(),
+            min: window,
+        });
+    }
+    if let Some(index) = samples
+        .windows(2)
+        .position(|pair| pair[0].time >= pair[1].time)
+    {
+        return Err(Error::TableUnsorted { index: index + 1 });
+    }
+
+    let atmosphere = simulation.atmosphere();
+    let projectile = &simulation.projectile;
+    let scale = (projectile.weight * 2.0 / (atmosphere.rho() * projectile.area())).get::<meter>();
+    let sound_velocity = atmosphere.sound_velocity().get::<meter_per_second>();
+
+    let half = window / 2;
+    let points = (0..samples.len())
+        .map(|i| {
+            let start = i.saturating_sub(half).min(samples.len() - window);
+            let (velocity, acceleration) = fit(&samples[start..start + window], samples[i].time);
+            Point {
+                mach: velocity / sound_velocity,
+                cd: -scale * acceleration / velocity.powi(2),
+                residual: samples[i].velocity - Velocity::new::<meter_per_second>(velocity),
+            }
+        })
+        .collect();
+    Ok(Analysis { points })
+}
+
+// Least squares quadratic through samples, centered on time, as (velocity, acceleration) there
+fn fit(samples: &[Sample], time: Time) -> (Numeric, Numeric) {
+    let mut s = [0.0; 5];
+    let mut r = [0.0; 3];
+    for sample in samples {
+        let dt = (sample.time - time).get::<second>();
+        let v = sample.velocity.get::<meter_per_second>();
+        let mut power = 1.0;
+        for (k, sum) in s.iter_mut().enumerate() {
+            *sum += power;
+            if k < 3 {
+                r[k] += v * power;
+            }
+            power *= dt;
+        }
+    }
+    // Normal equations, solved by Cramer's rule for the constant and linear terms
+    let m = [[s[0], s[1], s[2]], [s[1], s[2], s[3]], [s[2], s[3], s[4]]];
+    let det = |m: [[Numeric; 3]; 3]| {
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    };
+    let column = |k: usize| {
+        let mut m = m;
+        for (row, value) in m.iter_mut().zip(r) {
+            row[k] = value;
+        }
+        m
+    };
+    let d = det(m);
+    (det(column(0)) / d, det(column(1)) / d)
+}
+
+impl Analysis {
+    // Root mean square difference between measured and smoothed velocities
+    pub fn residual(&self) -> Velocity {
+        let squares = self
+            .points
+            .iter()
+            .map(|point| point.residual.get::<meter_per_second>().powi(2))
+            .sum::<Numeric>();
+        Velocity::new::<meter_per_second>((squares / self.points.len() as Numeric).sqrt())
+    }
+
+    // Mach range covered by the track, as (min, max)
+    // The curve from drag is held at its end values outside of this
+    pub fn coverage(&self) -> (Numeric, Numeric) {
+        self.points.iter().fold(
+            (Numeric::INFINITY, Numeric::NEG_INFINITY),
+            |(min, max), point| (min.min(point.mach), max.max(point.mach)),
+        )
+    }
+
+    // Curve for use with set_drag, by ascending mach, keeping the first point at any repeated mach
+    // As cd is the projectile's own, its bc should be set to its sectional density
+    pub fn drag(&self) -> Result<custom::Drag> {
+        let mut table = self
+            .points
+            .iter()
+            .map(|point| (point.mach, point.cd))
+            .collect::<Vec<_>>();
+        table.sort_by(|a, b| a.0.total_cmp(&b.0));
+        table.dedup_by(|a, b| a.0 == b.0);
+        custom::Drag::from_table(&table)
+    }
+}
@@ -0,0 +1,31 @@
+use crate::{Numeric, consts::PI};
+
+// SplitMix64 generator - small, fast, and reproducible from its seed on every platform
+#[derive(Debug, Clone)]
+pub(crate) struct Rng(u64);
+
+impl Rng {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    // Uniform on (0, 1], using the upper 53 bits to fill the mantissa
+    pub(crate) fn uniform(&mut self) -> Numeric {
+        ((self.next_u64() >> 11) + 1) as Numeric / (1u64 << 53) as Numeric
+    }
+
+    // Standard normal, Box-Muller transform
+    pub(crate) fn normal(&mut self) -> Numeric {
+        let r = (-2.0 * self.uniform().ln()).sqrt();
+        let theta = 2.0 * PI * self.uniform();
+        r * theta.cos()
+    }
+}
@@ -1,18 +1,24 @@
 use crate::{
     Numeric,
     consts::{FRAC_PI_2, PI},
+    drag::{DragTable, validate_table},
     error::{Error, Result},
     physics::DragInit,
     units::{
-        Angle, ConstZero, Length, Mass, Pressure, ThermodynamicTemperature, Time, Velocity,
-        angle::radian, length::meter, mass::kilogram, pressure::pascal,
-        thermodynamic_temperature::degree_celsius, time::second, velocity::meter_per_second,
+        Angle, AngularVelocity, ConstZero, Length, Mass, MomentOfInertia, Pressure,
+        TemperatureInterval, ThermodynamicTemperature, Time, Velocity, angle::radian,
+        angular_velocity::radian_per_second, length::meter, mass::kilogram,
+        moment_of_inertia::kilogram_square_meter, pressure::pascal, ratio::ratio,
+        temperature_interval, thermodynamic_temperature::degree_celsius, time::second,
+        velocity::meter_per_second,
     },
 };
 
 #[derive(Debug)]
 pub struct Simulation<D> {
     pub(crate) drag: Option<D>,
+    pub(crate) model: Model,
+    pub(crate) frame: Frame,
     pub(crate) flags: Flags,
     pub(crate) projectile: Projectile,
     pub(crate) scope: Scope,
@@ -21,30 +27,163 @@ pub struct Simulation<D> {
     pub(crate) time_step: Time,
 }
 
-#[derive(Debug)]
+// PointMass: drag, gravity and coriolis only
+// ModifiedPointMass: also tracks spin and yaw of repose, adding lift, magnus and yaw drag
+// RigidBody: six degrees of freedom, integrating orientation and angular velocity under aerodynamic
+// moments, needs a time step small enough to resolve yawing motion (~10us)
+// Spin drift and aerodynamic jump emerge from the latter models, so their empirical flags are rejected
+#[derive(Debug, Clone, Copy)]
+pub enum Model {
+    PointMass,
+    ModifiedPointMass,
+    RigidBody,
+}
+
+#[derive(Debug, Clone)]
 pub struct Atmosphere {
     pub(crate) temperature: ThermodynamicTemperature,
     pub(crate) pressure: Pressure,
     pub(crate) humidity: Numeric,
-    pub(crate) wind: Wind,
+    pub(crate) altitude: Length,
+    pub(crate) wind: WindProfile,
+    pub(crate) input: AtmosphereInput,
 }
 
-#[derive(Debug)]
+// Atmosphere as given to the builder, resolved into the station's altitude, temperature, pressure
+// and humidity by init, so that setters can be called in any order
+// Temperature or pressure not given is the standard atmosphere's at the station's altitude, plus
+// any offset, and humidity not given is dry air
+// Inputs giving the same quantity in different ways conflict, as do density altitude, which is
+// taken to be of dry air, and humidity
+#[derive(Debug, Clone, Default)]
+pub(crate) struct AtmosphereInput {
+    pub(crate) altitude: Option<Length>,
+    pub(crate) temperature: Option<ThermodynamicTemperature>,
+    pub(crate) temperature_offset: Option<TemperatureInterval>,
+    pub(crate) pressure: Option<Pressure>,
+    pub(crate) pressure_offset: Option<Pressure>,
+    pub(crate) barometric_pressure: Option<(Pressure, Length)>,
+    pub(crate) density_altitude: Option<Length>,
+    pub(crate) humidity: Option<Numeric>,
+    pub(crate) dew_point: Option<ThermodynamicTemperature>,
+    pub(crate) wet_bulb: Option<ThermodynamicTemperature>,
+}
+
+// Flat: local tangent plane at the shooter, with gravity along a constant down vector
+// EarthCentered: spherical earth, with gravity towards its center falling off with the square of
+// the distance, and the centrifugal term varying along the path.  Height above the surface drives
+// lapse and wind gradient, and distance is measured along the line of sight curved with the surface
+// at the shooter's altitude, which for a level shot is the surface itself
+#[derive(Debug, Clone, Copy)]
+pub enum Frame {
+    Flat,
+    EarthCentered,
+}
+
+#[derive(Debug, Clone)]
 pub struct Flags {
     pub(crate) coriolis: bool,
     pub(crate) drag: bool,
     pub(crate) gravity: bool,
+    pub(crate) lapse: bool,
+    pub(crate) spin_drift: bool,
+    pub(crate) aerodynamic_jump: bool,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Projectile {
     pub caliber: Length,
     pub weight: Mass,
     pub bc: Numeric,
+    // Ballistic coefficients by ascending velocity threshold, each applying from its threshold up to
+    // the next, and the lowest also below it.  Used in place of bc when given, so bc can be left out
+    pub bc_bands: Vec<(Velocity, Numeric)>,
     pub velocity: Velocity,
+    pub powder: Powder,
+    pub barrel: Barrel,
+    pub length: Length,
+    pub twist: Length,
+    pub twist_direction: TwistDirection,
+    pub spin: AngularVelocity,
+    pub axial_inertia: MomentOfInertia,
+    pub transverse_inertia: MomentOfInertia,
+    pub coefficients: Coefficients,
 }
 
-#[derive(Debug)]
+// Muzzle velocity's dependence on powder temperature
+// The projectile's velocity is taken to be measured at the reference temperature
+#[derive(Debug, Clone)]
+pub struct Powder {
+    // Powder temperature the muzzle velocity was measured at
+    pub reference: ThermodynamicTemperature,
+    // Current powder temperature, the air temperature if not given
+    pub temperature: Option<ThermodynamicTemperature>,
+    pub sensitivity: PowderSensitivity,
+}
+
+// Constant: muzzle velocity doesn't change with powder temperature
+// Linear: change in muzzle velocity per change in powder temperature
+// Table: muzzle velocities measured over ascending powder temperatures, clamped to its ends
+// Only the change from the reference temperature is applied, so a table measured with another
+// lot or barrel can still be used
+#[derive(Debug, Clone)]
+pub enum PowderSensitivity {
+    Constant,
+    Linear {
+        velocity: Velocity,
+        interval: TemperatureInterval,
+    },
+    Table(Vec<(ThermodynamicTemperature, Velocity)>),
+}
+
+// Muzzle velocity's dependence on barrel length
+// The projectile's velocity is taken to be measured with the test barrel
+#[derive(Debug, Clone)]
+pub struct Barrel {
+    // Length of the barrel the muzzle velocity was measured with, no adjustment is made without it
+    pub test: Option<Length>,
+    // Length of the barrel fired, the test barrel's if not given
+    pub actual: Option<Length>,
+    pub adjustment: BarrelAdjustment,
+}
+
+// Constant: muzzle velocity doesn't change with barrel length
+// Linear: change in muzzle velocity per change in barrel length
+// Table: muzzle velocities measured over ascending barrel lengths, clamped to its ends
+// As with powder, only the change from the test barrel is applied
+#[derive(Debug, Clone)]
+pub enum BarrelAdjustment {
+    Constant,
+    Linear { velocity: Velocity, length: Length },
+    Table(Vec<(Length, Velocity)>),
+}
+
+// Aerodynamic coefficients beyond drag, derivatives are per radian of yaw
+#[derive(Debug, Clone, Copy)]
+pub struct Coefficients {
+    // Lift force (CLa)
+    pub lift: Numeric,
+    // Overturning moment (CMa)
+    pub overturning: Numeric,
+    // Magnus force (CNpa)
+    pub magnus: Numeric,
+    // Spin damping moment (Clp), negative
+    pub spin_damping: Numeric,
+    // Drag due to yaw (CDa2)
+    pub yaw_drag: Numeric,
+    // Pitch damping moment (CMq + CMa-dot), negative
+    pub pitch_damping: Numeric,
+    // Magnus moment (CMpa)
+    pub magnus_moment: Numeric,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum TwistDirection {
+    Right,
+    Left,
+}
+
+#[derive(Debug, Clone)]
 pub struct Scope {
     pub(crate) yaw: Angle,
     pub(crate) pitch: Angle,
@@ -53,38 +192,133 @@ pub struct Scope {
     pub(crate) offset: Length,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Shooter {
     pub(crate) yaw: Angle,
     pub(crate) pitch: Angle,
     pub(crate) roll: Angle,
     pub(crate) latitude: Angle,
+    pub(crate) gravity: GravityModel,
 }
 
-#[derive(Debug)]
+// Standard: constant standard gravity, 9.80665 m/s^2
+// Normal: WGS-84 normal gravity at the shooter's latitude (Somigliana), reduced for the shooter's
+// altitude above the ellipsoid (free-air)
+#[derive(Debug, Clone, Copy)]
+pub enum GravityModel {
+    Standard,
+    Normal,
+}
+
+// Wind zones, ordered by the downrange distance at which each begins
+#[derive(Debug, Clone)]
+pub struct WindProfile {
+    pub(crate) zones: Vec<Wind>,
+    pub(crate) gradient: WindGradient,
+    pub(crate) gust: Gust,
+}
+
+// Time-varying deviation from the mean wind, an Ornstein-Uhlenbeck process in each axis
+// Intensity is the standard deviation of gust speed, correlation the time over which gusts persist
+// Disabled while intensity is zero
+#[derive(Debug, Clone)]
+pub struct Gust {
+    pub(crate) intensity: Velocity,
+    pub(crate) correlation: Time,
+    pub(crate) seed: u64,
+}
+
+// Frame of reference wind direction and pitch are measured in, per zone
+// LineOfSight: yaw from the shooter's bearing, pitch from the line of sight
+// World: yaw from north, pitch from the horizon
+#[derive(Debug, Clone, Copy)]
+pub enum WindFrame {
+    LineOfSight,
+    World,
+}
+
+// Boundary layer profile scaling wind speed with height above ground, from the speed measured at
+// reference height.  The firing point is taken to be at the reference height
+#[derive(Debug, Clone, Copy)]
+pub enum WindGradient {
+    Uniform,
+    PowerLaw { exponent: Numeric, height: Length },
+    Logarithmic { roughness: Length, height: Length },
+}
+
+#[derive(Debug, Clone)]
 pub struct Wind {
     pub(crate) yaw: Angle,
     pub(crate) pitch: Angle,
     pub(crate) velocity: Velocity,
+    pub(crate) range: Length,
+    pub(crate) frame: WindFrame,
 }
 
 #[derive(Debug)]
 pub struct SimulationBuilder<D>(Simulation<D>);
 
+impl WindProfile {
+    pub(crate) fn last(&self) -> &Wind {
+        self.zones.last().unwrap()
+    }
+
+    pub(crate) fn last_mut(&mut self) -> &mut Wind {
+        self.zones.last_mut().unwrap()
+    }
+}
+
+impl<D> Simulation<D> {
+    pub fn atmosphere(&self) -> &Atmosphere {
+        &self.atmosphere
+    }
+}
+
 impl<D> Default for SimulationBuilder<D> {
     fn default() -> Self {
         Self(Simulation {
             drag: None,
+            model: Model::PointMass,
+            frame: Frame::Flat,
             flags: Flags {
                 coriolis: true,
                 drag: true,
                 gravity: true,
+                lapse: false,
+                spin_drift: false,
+                aerodynamic_jump: false,
             },
             projectile: Projectile {
                 caliber: Length::ZERO,
                 weight: Mass::ZERO,
                 bc: Numeric::ZERO,
+                bc_bands: Vec::new(),
                 velocity: Velocity::ZERO,
+                length: Length::ZERO,
+                twist: Length::ZERO,
+                twist_direction: TwistDirection::Right,
+                spin: AngularVelocity::ZERO,
+                axial_inertia: MomentOfInertia::ZERO,
+                transverse_inertia: MomentOfInertia::ZERO,
+                powder: Powder {
+                    reference: ThermodynamicTemperature::new::<degree_celsius>(15.0),
+                    temperature: None,
+                    sensitivity: PowderSensitivity::Constant,
+                },
+                barrel: Barrel {
+                    test: None,
+                    actual: None,
+                    adjustment: BarrelAdjustment::Constant,
+                },
+                coefficients: Coefficients {
+                    lift: Numeric::ZERO,
+                    overturning: Numeric::ZERO,
+                    magnus: Numeric::ZERO,
+                    spin_damping: Numeric::ZERO,
+                    yaw_drag: Numeric::ZERO,
+                    pitch_damping: Numeric::ZERO,
+                    magnus_moment: Numeric::ZERO,
+                },
             },
             scope: Scope {
                 yaw: Angle::ZERO,
@@ -94,13 +328,25 @@ impl<D> Default for SimulationBuilder<D> {
                 offset: Length::ZERO,
             },
             atmosphere: Atmosphere {
-                temperature: ThermodynamicTemperature::ZERO,
-                pressure: Pressure::ZERO,
+                temperature: Atmosphere::standard_temperature(Length::ZERO),
+                pressure: Atmosphere::standard_pressure(Length::ZERO),
                 humidity: Numeric::ZERO,
-                wind: Wind {
-                    yaw: Angle::ZERO,
-                    pitch: Angle::ZERO,
-                    velocity: Velocity::ZERO,
+                altitude: Length::ZERO,
+                input: AtmosphereInput::default(),
+                wind: WindProfile {
+                    zones: vec![Wind {
+                        yaw: Angle::ZERO,
+                        pitch: Angle::ZERO,
+                        velocity: Velocity::ZERO,
+                        range: Length::ZERO,
+                        frame: WindFrame::LineOfSight,
+                    }],
+                    gradient: WindGradient::Uniform,
+                    gust: Gust {
+                        intensity: Velocity::ZERO,
+                        correlation: Time::ZERO,
+                        seed: 0,
+                    },
                 },
             },
             shooter: Shooter {
@@ -108,6 +354,7 @@ impl<D> Default for SimulationBuilder<D> {
                 pitch: Angle::ZERO,
                 roll: Angle::ZERO,
                 latitude: Angle::ZERO,
+                gravity: GravityModel::Standard,
             },
             time_step: Time::ZERO,
         })
@@ -118,9 +365,283 @@ impl<D> SimulationBuilder<D>
 where
     D: DragInit,
 {
-    pub fn init(mut self) -> Simulation<D> {
-        self.0.drag = Some(D::new(&self.0));
-        self.0
+    // Fails for models given at runtime, such as drag::custom, if not given through set_drag,
+    // and if inputs the model needs are missing
+    pub fn init(mut self) -> Result<Simulation<D>> {
+        self.resolve_atmosphere()?;
+        self.validate()?;
+        self.0.drag = Some(D::new(&self.0)?);
+        Ok(self.0)
+    }
+
+    // As init, but rejects loads which are not gyroscopically stable (Sg < 1.0)
+    pub fn try_init(mut self) -> Result<Simulation<D>> {
+        self.resolve_atmosphere()?;
+        self.validate_stability()?;
+        let stability = self.0.stability();
+        if stability < 1.0 {
+            Err(Error::Unstable { stability })
+        } else {
+            self.init()
+        }
+    }
+}
+
+impl<D> SimulationBuilder<D> {
+    // Station's altitude, temperature, pressure and humidity from the inputs given
+    fn resolve_atmosphere(&mut self) -> Result<()> {
+        let altitude = self.altitude()?;
+        let temperature = self.temperature(altitude)?;
+        let pressure = self.pressure(altitude, temperature)?;
+        let humidity = self.humidity(temperature, pressure)?;
+        let atmosphere = &mut self.0.atmosphere;
+        atmosphere.altitude = altitude;
+        atmosphere.temperature = temperature;
+        atmosphere.pressure = pressure;
+        atmosphere.humidity = humidity;
+        Ok(())
+    }
+
+    // Barometric pressure is given with the altitude it was corrected from, which is the station's
+    fn altitude(&self) -> Result<Length> {
+        let AtmosphereInput {
+            altitude,
+            barometric_pressure,
+            ..
+        } = self.0.atmosphere.input;
+        match (altitude, barometric_pressure) {
+            (Some(altitude), Some((_, barometric))) if altitude != barometric => {
+                Err(Error::Conflict {
+                    first: "altitude",
+                    second: "barometric_pressure",
+                })
+            }
+            (Some(altitude), _) | (None, Some((_, altitude))) => Ok(altitude),
+            (None, None) => Ok(Length::ZERO),
+        }
+    }
+
+    fn temperature(&self, altitude: Length) -> Result<ThermodynamicTemperature> {
+        let AtmosphereInput {
+            temperature,
+            temperature_offset,
+            ..
+        } = self.0.atmosphere.input;
+        exclusive(&[
+            ("temperature", temperature.is_some()),
+            ("temperature_offset", temperature_offset.is_some()),
+        ])?;
+        let standard = Atmosphere::standard_temperature(altitude);
+        let value = match (temperature, temperature_offset) {
+            (Some(temperature), _) => temperature,
+            (_, Some(offset)) => standard + offset,
+            (None, None) => standard,
+        };
+        let min = ThermodynamicTemperature::new::<degree_celsius>(-80.0);
+        let max = ThermodynamicTemperature::new::<degree_celsius>(50.0);
+        if value >= min && value <= max {
+            Ok(value)
+        } else {
+            Err(Error::ThermodynamicTemperatureOutOfRange { value, min, max })
+        }
+    }
+
+    // Density altitude is converted to the pressure of dry air at temperature with the standard
+    // atmosphere's density there
+    fn pressure(
+        &self,
+        altitude: Length,
+        temperature: ThermodynamicTemperature,
+    ) -> Result<Pressure> {
+        let AtmosphereInput {
+            pressure,
+            pressure_offset,
+            barometric_pressure,
+            density_altitude,
+            ..
+        } = self.0.atmosphere.input;
+        exclusive(&[
+            ("pressure", pressure.is_some()),
+            ("pressure_offset", pressure_offset.is_some()),
+            ("barometric_pressure", barometric_pressure.is_some()),
+            ("density_altitude", density_altitude.is_some()),
+        ])?;
+        let standard = Atmosphere::standard_pressure(altitude);
+        let value = match (
+            pressure,
+            pressure_offset,
+            barometric_pressure,
+            density_altitude,
+        ) {
+            (Some(pressure), ..) => pressure,
+            (_, Some(offset), ..) => standard + offset,
+            (.., Some((barometric, _)), _) => barometric / Atmosphere::barometric_ratio(altitude),
+            (.., Some(density_altitude)) => {
+                Atmosphere::dry_pressure(temperature, Atmosphere::standard_rho(density_altitude))
+            }
+            (None, None, None, None) => standard,
+        };
+        if value > Pressure::ZERO {
+            Ok(value)
+        } else {
+            Err(Error::PositiveExpected {
+                value: value.get::<pascal>(),
+            })
+        }
+    }
+
+    // Dew point is converted through the saturation pressure at temperature, and wet bulb through
+    // the psychrometric equation at temperature and pressure
+    fn humidity(
+        &self,
+        temperature: ThermodynamicTemperature,
+        pressure: Pressure,
+    ) -> Result<Numeric> {
+        let AtmosphereInput {
+            density_altitude,
+            humidity,
+            dew_point,
+            wet_bulb,
+            ..
+        } = self.0.atmosphere.input;
+        exclusive(&[
+            ("density_altitude", density_altitude.is_some()),
+            ("humidity", humidity.is_some()),
+            ("dew_point", dew_point.is_some()),
+            ("wet_bulb", wet_bulb.is_some()),
+        ])?;
+        let saturation = Atmosphere::saturation_pressure(temperature);
+        let value = match (humidity, dew_point, wet_bulb) {
+            (Some(humidity), ..) => humidity,
+            (_, Some(dew_point), _) => {
+                (Atmosphere::saturation_pressure(dew_point) / saturation).get::<ratio>()
+            }
+            (.., Some(wet_bulb)) => {
+                (Atmosphere::psychrometric_pressure(temperature, wet_bulb, pressure) / saturation)
+                    .get::<ratio>()
+            }
+            (None, None, None) => 0.0,
+        };
+        let min = 0.0;
+        let max = 1.0;
+        if value >= min && value <= max {
+            Ok(value)
+        } else {
+            Err(Error::NumericOutOfRange { value, min, max })
+        }
+    }
+
+    // Inputs left at their defaults of zero would otherwise turn the trajectory into NaN
+    fn validate(&self) -> Result<()> {
+        let projectile = &self.0.projectile;
+        // Drag tables are prescaled by dividing by bc
+        if self.0.flags.drag && projectile.reference_bc() == 0.0 {
+            return Err(Error::PositiveExpected {
+                value: projectile.bc,
+            });
+        }
+        if self.0.flags.spin_drift || self.0.flags.aerodynamic_jump {
+            self.validate_stability()?;
+        }
+        // Spin drift and aerodynamic jump emerge from the models tracking spin, so the empirical
+        // terms would count them twice
+        let spinning = !matches!(self.0.model, Model::PointMass);
+        if spinning && self.0.flags.spin_drift {
+            return Err(Error::Conflict {
+                first: "model",
+                second: "spin_drift",
+            });
+        }
+        if spinning && self.0.flags.aerodynamic_jump {
+            return Err(Error::Conflict {
+                first: "model",
+                second: "aerodynamic_jump",
+            });
+        }
+        match self.0.model {
+            Model::PointMass => {}
+            Model::ModifiedPointMass => self.validate_spin()?,
+            Model::RigidBody => {
+                self.validate_spin()?;
+                // Precession divides by transverse inertia
+                if projectile.transverse_inertia == MomentOfInertia::ZERO {
+                    return Err(Error::PositiveExpected {
+                        value: projectile.transverse_inertia.get::<kilogram_square_meter>(),
+                    });
+                }
+                // Yawing motion isn't resolved by longer steps, and grows without bound
+                let min = Time::ZERO;
+                let max = Time::new::<second>(10e-6);
+                let value = self.0.time_step;
+                if !(value > min && value <= max) {
+                    return Err(Error::TimeOutOfRange { value, min, max });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Stability divides by twist, length and caliber
+    fn validate_stability(&self) -> Result<()> {
+        let projectile = &self.0.projectile;
+        for value in [projectile.caliber, projectile.length, projectile.twist] {
+            if value == Length::ZERO {
+                return Err(Error::PositiveExpected {
+                    value: value.get::<meter>(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    // Spin is divided by twist when not given, and yaw of repose and spin decay by caliber,
+    // overturning and axial inertia
+    fn validate_spin(&self) -> Result<()> {
+        let projectile = &self.0.projectile;
+        if projectile.spin == AngularVelocity::ZERO && projectile.twist == Length::ZERO {
+            return Err(Error::PositiveExpected {
+                value: projectile.twist.get::<meter>(),
+            });
+        }
+        if projectile.caliber == Length::ZERO {
+            return Err(Error::PositiveExpected {
+                value: projectile.caliber.get::<meter>(),
+            });
+        }
+        if projectile.axial_inertia == MomentOfInertia::ZERO {
+            return Err(Error::PositiveExpected {
+                value: projectile.axial_inertia.get::<kilogram_square_meter>(),
+            });
+        }
+        if projectile.coefficients.overturning == 0.0 {
+            return Err(Error::PositiveExpected {
+                value: projectile.coefficients.overturning,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl<D> Simulation<D> {
+    // Same configuration with another drag model, such as the same table interpolated differently,
+    // to compare trajectories.  Limited to the built in tables, as models given through set_drag
+    // have no table to switch to
+    pub fn with_drag<E>(&self) -> Result<Simulation<E>>
+    where
+        E: DragInit + DragTable,
+    {
+        let simulation = Simulation {
+            drag: None,
+            model: self.model,
+            frame: self.frame,
+            flags: self.flags.clone(),
+            projectile: self.projectile.clone(),
+            scope: self.scope.clone(),
+            atmosphere: self.atmosphere.clone(),
+            shooter: self.shooter.clone(),
+            time_step: self.time_step,
+        };
+        SimulationBuilder(simulation).init()
     }
 }
 
@@ -140,12 +661,28 @@ impl<D> SimulationBuilder<D> {
         }
     }
 
+    // Drag model data, for models given at runtime such as drag::custom
+    pub fn set_drag(mut self, value: D) -> Self {
+        self.0.drag = Some(value);
+        self
+    }
+
+    pub fn set_model(mut self, value: Model) -> Self {
+        self.0.model = value;
+        self
+    }
+
+    pub fn set_frame(mut self, value: Frame) -> Self {
+        self.0.frame = value;
+        self
+    }
+
     // Atmosphere
     pub fn set_temperature(mut self, value: ThermodynamicTemperature) -> Result<Self> {
         let min = ThermodynamicTemperature::new::<degree_celsius>(-80.0);
         let max = ThermodynamicTemperature::new::<degree_celsius>(50.0);
         if value >= min && value <= max {
-            self.0.atmosphere.temperature = value;
+            self.0.atmosphere.input.temperature = Some(value);
             Ok(self)
         } else {
             Err(Error::ThermodynamicTemperatureOutOfRange { value, min, max })
@@ -154,12 +691,73 @@ impl<D> SimulationBuilder<D> {
 
     pub fn set_pressure(mut self, value: Pressure) -> Result<Self> {
         if value.is_sign_positive() {
-            self.0.atmosphere.pressure = value;
+            self.0.atmosphere.input.pressure = Some(value);
+            Ok(self)
+        } else {
+            Err(Error::PositiveExpected {
+                value: value.get::<pascal>(),
+            })
+        }
+    }
+
+    // Temperature and pressure not given are the standard atmosphere's at the station's altitude
+    pub fn set_altitude(mut self, value: Length) -> Result<Self> {
+        let min = Atmosphere::STANDARD_ALTITUDE_MIN;
+        let max = Atmosphere::STANDARD_ALTITUDE_MAX;
+        if value >= min && value <= max {
+            self.0.atmosphere.input.altitude = Some(value);
             Ok(self)
         } else {
+            Err(Error::LengthOutOfRange { value, min, max })
+        }
+    }
+
+    // Deviation from standard temperature at the altitude given by set_altitude, in place of
+    // set_temperature
+    pub fn set_temperature_offset(mut self, value: TemperatureInterval) -> Self {
+        self.0.atmosphere.input.temperature_offset = Some(value);
+        self
+    }
+
+    // Deviation from standard pressure at the altitude given by set_altitude, in place of
+    // set_pressure
+    pub fn set_pressure_offset(mut self, value: Pressure) -> Self {
+        self.0.atmosphere.input.pressure_offset = Some(value);
+        self
+    }
+
+    // Sea level corrected pressure, as given in weather reports, converted at init to station
+    // pressure at the altitude it was corrected from, which is taken as the station's
+    pub fn set_barometric_pressure(mut self, value: Pressure, altitude: Length) -> Result<Self> {
+        let min = Atmosphere::STANDARD_ALTITUDE_MIN;
+        let max = Atmosphere::STANDARD_ALTITUDE_MAX;
+        if !value.is_sign_positive() {
             Err(Error::PositiveExpected {
                 value: value.get::<pascal>(),
             })
+        } else if altitude >= min && altitude <= max {
+            self.0.atmosphere.input.barometric_pressure = Some((value, altitude));
+            Ok(self)
+        } else {
+            Err(Error::LengthOutOfRange {
+                value: altitude,
+                min,
+                max,
+            })
+        }
+    }
+
+    // In place of pressure and humidity, converted at init to dry air at the station's
+    // temperature, at the pressure giving the standard atmosphere's density at the density
+    // altitude.  The station stays at the altitude given by set_altitude
+    pub fn set_density_altitude(mut self, value: Length) -> Result<Self> {
+        let min = Atmosphere::STANDARD_ALTITUDE_MIN;
+        let max = Atmosphere::STANDARD_ALTITUDE_MAX;
+        if value >= min && value <= max {
+            self.0.atmosphere.input.density_altitude = Some(value);
+            Ok(self)
+        } else {
+            Err(Error::LengthOutOfRange { value, min, max })
         }
     }
 
@@ -167,13 +765,25 @@ impl<D> SimulationBuilder<D> {
         let min = 0.0;
         let max = 1.0;
         if value >= min && value <= max {
-            self.0.atmosphere.humidity = value;
+            self.0.atmosphere.input.humidity = Some(value);
             Ok(self)
         } else {
             Err(Error::NumericOutOfRange { value, min, max })
         }
     }
 
+    // In place of set_humidity, converted at init through the station's temperature
+    pub fn set_dew_point(mut self, value: ThermodynamicTemperature) -> Self {
+        self.0.atmosphere.input.dew_point = Some(value);
+        self
+    }
+
+    // In place of set_humidity, converted at init through the station's temperature and pressure
+    pub fn set_wet_bulb(mut self, value: ThermodynamicTemperature) -> Self {
+        self.0.atmosphere.input.wet_bulb = Some(value);
+        self
+    }
+
     // Flags
     pub fn use_coriolis(mut self, value: bool) -> Self {
         self.0.flags.coriolis = value;
@@ -190,6 +800,24 @@ impl<D> SimulationBuilder<D> {
         self
     }
 
+    // Evaluate density and speed of sound at the projectile's height, rather than once at the station
+    pub fn use_lapse(mut self, value: bool) -> Self {
+        self.0.flags.lapse = value;
+        self
+    }
+
+    // Litz's empirical gyroscopic drift, requires twist and length of projectile
+    pub fn use_spin_drift(mut self, value: bool) -> Self {
+        self.0.flags.spin_drift = value;
+        self
+    }
+
+    // Litz's empirical vertical jump from crosswind, requires twist and length of projectile
+    pub fn use_aerodynamic_jump(mut self, value: bool) -> Self {
+        self.0.flags.aerodynamic_jump = value;
+        self
+    }
+
     // Shooter
     pub fn set_incline(mut self, value: Angle) -> Result<Self> {
         let min = Angle::new::<radian>(-FRAC_PI_2);
@@ -213,6 +841,11 @@ impl<D> SimulationBuilder<D> {
         }
     }
 
+    pub fn set_gravity_model(mut self, value: GravityModel) -> Self {
+        self.0.shooter.gravity = value;
+        self
+    }
+
     pub fn set_bearing(mut self, value: Angle) -> Result<Self> {
         let min = Angle::new::<radian>(-2.0 * PI);
         let max = Angle::new::<radian>(2.0 * PI);
@@ -225,9 +858,28 @@ impl<D> SimulationBuilder<D> {
     }
 
     // Wind
+    // Speed and direction apply to the most recently added zone, or the muzzle zone if none were added
+    pub fn add_wind_zone(mut self, value: Length) -> Result<Self> {
+        let min = self.0.atmosphere.wind.last().range;
+        let max = Length::new::<meter>(Numeric::INFINITY);
+        if value > min && value < max {
+            let frame = self.0.atmosphere.wind.last().frame;
+            self.0.atmosphere.wind.zones.push(Wind {
+                yaw: Angle::ZERO,
+                pitch: Angle::ZERO,
+                velocity: Velocity::ZERO,
+                range: value,
+                frame,
+            });
+            Ok(self)
+        } else {
+            Err(Error::LengthOutOfRange { value, min, max })
+        }
+    }
+
     pub fn set_wind_speed(mut self, value: Velocity) -> Result<Self> {
         if value.is_sign_positive() {
-            self.0.atmosphere.wind.velocity = value;
+            self.0.atmosphere.wind.last_mut().velocity = value;
             Ok(self)
         } else {
             Err(Error::PositiveExpected {
@@ -240,13 +892,99 @@ impl<D> SimulationBuilder<D> {
         let min = Angle::new::<radian>(-2.0 * PI);
         let max = Angle::new::<radian>(2.0 * PI);
         if value >= min && value <= max {
-            self.0.atmosphere.wind.yaw = value;
+            self.0.atmosphere.wind.last_mut().yaw = value;
             Ok(self)
         } else {
             Err(Error::AngleOutOfRange { value, min, max })
         }
     }
 
+    // Elevation angle of wind flow, positive for updrafts
+    pub fn set_wind_pitch(mut self, value: Angle) -> Result<Self> {
+        let min = Angle::new::<radian>(-FRAC_PI_2);
+        let max = Angle::new::<radian>(FRAC_PI_2);
+        if value >= min && value <= max {
+            self.0.atmosphere.wind.last_mut().pitch = value;
+            Ok(self)
+        } else {
+            Err(Error::AngleOutOfRange { value, min, max })
+        }
+    }
+
+    // Components of wind flow in the world frame, which this selects for the zone
+    pub fn set_wind_components(mut self, north: Velocity, east: Velocity, up: Velocity) -> Self {
+        let horizontal = (north * north + east * east).sqrt();
+        let velocity = (horizontal * horizontal + up * up).sqrt();
+        let wind = self.0.atmosphere.wind.last_mut();
+        wind.velocity = velocity;
+        wind.yaw = (-east).atan2(north);
+        wind.pitch = up.atan2(horizontal);
+        wind.frame = WindFrame::World;
+        self
+    }
+
+    pub fn set_wind_frame(mut self, value: WindFrame) -> Self {
+        self.0.atmosphere.wind.last_mut().frame = value;
+        self
+    }
+
+    pub fn set_wind_gradient(mut self, value: WindGradient) -> Result<Self> {
+        match value {
+            WindGradient::Uniform => {}
+            WindGradient::PowerLaw { exponent, height } => {
+                if !exponent.is_sign_positive() {
+                    return Err(Error::PositiveExpected { value: exponent });
+                }
+                if height <= Length::ZERO {
+                    return Err(Error::PositiveExpected {
+                        value: height.get::<meter>(),
+                    });
+                }
+            }
+            WindGradient::Logarithmic { roughness, height } => {
+                let min = Length::ZERO;
+                let max = height;
+                if !(roughness > min && roughness < max) {
+                    return Err(Error::LengthOutOfRange {
+                        value: roughness,
+                        min,
+                        max,
+                    });
+                }
+            }
+        }
+        self.0.atmosphere.wind.gradient = value;
+        Ok(self)
+    }
+
+    pub fn set_gust_intensity(mut self, value: Velocity) -> Result<Self> {
+        if value.is_sign_positive() {
+            self.0.atmosphere.wind.gust.intensity = value;
+            Ok(self)
+        } else {
+            Err(Error::PositiveExpected {
+                value: value.get::<meter_per_second>(),
+            })
+        }
+    }
+
+    pub fn set_gust_correlation(mut self, value: Time) -> Result<Self> {
+        if value > Time::ZERO {
+            self.0.atmosphere.wind.gust.correlation = value;
+            Ok(self)
+        } else {
+            Err(Error::PositiveExpected {
+                value: value.get::<second>(),
+            })
+        }
+    }
+
+    // Same seed and time step replay the same gusts, regardless of projectile
+    pub fn set_gust_seed(mut self, value: u64) -> Self {
+        self.0.atmosphere.wind.gust.seed = value;
+        self
+    }
+
     //Scope
     pub fn set_scope_height(mut self, value: Length) -> Self {
         self.0.scope.height = value;
@@ -296,6 +1034,86 @@ impl<D> SimulationBuilder<D> {
         }
     }
 
+    // Powder temperature the velocity given by set_velocity was measured at
+    pub fn set_powder_reference(mut self, value: ThermodynamicTemperature) -> Result<Self> {
+        let min = ThermodynamicTemperature::new::<degree_celsius>(-80.0);
+        let max = ThermodynamicTemperature::new::<degree_celsius>(80.0);
+        if value >= min && value <= max {
+            self.0.projectile.powder.reference = value;
+            Ok(self)
+        } else {
+            Err(Error::ThermodynamicTemperatureOutOfRange { value, min, max })
+        }
+    }
+
+    // Current powder temperature, if it differs from the air temperature
+    pub fn set_powder_temperature(mut self, value: ThermodynamicTemperature) -> Result<Self> {
+        let min = ThermodynamicTemperature::new::<degree_celsius>(-80.0);
+        let max = ThermodynamicTemperature::new::<degree_celsius>(80.0);
+        if value >= min && value <= max {
+            self.0.projectile.powder.temperature = Some(value);
+            Ok(self)
+        } else {
+            Err(Error::ThermodynamicTemperatureOutOfRange { value, min, max })
+        }
+    }
+
+    pub fn set_powder_sensitivity(mut self, value: PowderSensitivity) -> Result<Self> {
+        match &value {
+            PowderSensitivity::Constant => {}
+            PowderSensitivity::Linear { interval, .. } => {
+                if !(interval.is_sign_positive() && *interval != TemperatureInterval::ZERO) {
+                    return Err(Error::PositiveExpected {
+                        value: interval.get::<temperature_interval::kelvin>(),
+                    });
+                }
+            }
+            PowderSensitivity::Table(table) => validate_table(table)?,
+        }
+        self.0.projectile.powder.sensitivity = value;
+        Ok(self)
+    }
+
+    // Barrel length the velocity given by set_velocity was measured with
+    pub fn set_barrel_test_length(mut self, value: Length) -> Result<Self> {
+        if value.is_sign_positive() {
+            self.0.projectile.barrel.test = Some(value);
+            Ok(self)
+        } else {
+            Err(Error::PositiveExpected {
+                value: value.get::<meter>(),
+            })
+        }
+    }
+
+    // Barrel length fired, if it differs from the test barrel
+    pub fn set_barrel_length(mut self, value: Length) -> Result<Self> {
+        if value.is_sign_positive() {
+            self.0.projectile.barrel.actual = Some(value);
+            Ok(self)
+        } else {
+            Err(Error::PositiveExpected {
+                value: value.get::<meter>(),
+            })
+        }
+    }
+
+    pub fn set_barrel_adjustment(mut self, value: BarrelAdjustment) -> Result<Self> {
+        match &value {
+            BarrelAdjustment::Constant => {}
+            BarrelAdjustment::Linear { length, .. } => {
+                if !(length.is_sign_positive() && *length != Length::ZERO) {
+                    return Err(Error::PositiveExpected {
+                        value: length.get::<meter>(),
+                    });
+                }
+            }
+            BarrelAdjustment::Table(table) => validate_table(table)?,
+        }
+        self.0.projectile.barrel.adjustment = value;
+        Ok(self)
+    }
+
     pub fn set_mass(mut self, value: Mass) -> Result<Self> {
         if value.is_sign_positive() {
             self.0.projectile.weight = value;
@@ -312,7 +1130,110 @@ impl<D> SimulationBuilder<D> {
             self.0.projectile.bc = value;
             Ok(self)
         } else {
-            Err(Error::PositiveExpected { value: value })
+            Err(Error::PositiveExpected { value })
         }
     }
+
+    // Ballistic coefficient from the velocity given and up, replacing any already given at it
+    pub fn add_bc_band(mut self, velocity: Velocity, bc: Numeric) -> Result<Self> {
+        if !velocity.is_sign_positive() {
+            return Err(Error::PositiveExpected {
+                value: velocity.get::<meter_per_second>(),
+            });
+        }
+        if !bc.is_sign_positive() || bc == 0.0 {
+            return Err(Error::PositiveExpected { value: bc });
+        }
+        let bands = &mut self.0.projectile.bc_bands;
+        let i = bands.partition_point(|&(threshold, _)| threshold < velocity);
+        match bands.get_mut(i) {
+            Some(band) if band.0 == velocity => band.1 = bc,
+            _ => bands.insert(i, (velocity, bc)),
+        }
+        Ok(self)
+    }
+
+    pub fn set_length(mut self, value: Length) -> Result<Self> {
+        if value.is_sign_positive() {
+            self.0.projectile.length = value;
+            Ok(self)
+        } else {
+            Err(Error::PositiveExpected {
+                value: value.get::<meter>(),
+            })
+        }
+    }
+
+    // Distance travelled per turn of rifling
+    pub fn set_twist(mut self, value: Length) -> Result<Self> {
+        if value.is_sign_positive() {
+            self.0.projectile.twist = value;
+            Ok(self)
+        } else {
+            Err(Error::PositiveExpected {
+                value: value.get::<meter>(),
+            })
+        }
+    }
+
+    pub fn set_twist_direction(mut self, value: TwistDirection) -> Self {
+        self.0.projectile.twist_direction = value;
+        self
+    }
+
+    // Spin rate at the muzzle, otherwise derived from twist and muzzle velocity
+    pub fn set_spin(mut self, value: AngularVelocity) -> Result<Self> {
+        if value.is_sign_positive() {
+            self.0.projectile.spin = value;
+            Ok(self)
+        } else {
+            Err(Error::PositiveExpected {
+                value: value.get::<radian_per_second>(),
+            })
+        }
+    }
+
+    // Moment of inertia about the projectile's axis of symmetry
+    pub fn set_axial_inertia(mut self, value: MomentOfInertia) -> Result<Self> {
+        if value.is_sign_positive() {
+            self.0.projectile.axial_inertia = value;
+            Ok(self)
+        } else {
+            Err(Error::PositiveExpected {
+                value: value.get::<kilogram_square_meter>(),
+            })
+        }
+    }
+
+    // Moment of inertia about an axis through the center of mass, normal to the axis of symmetry
+    pub fn set_transverse_inertia(mut self, value: MomentOfInertia) -> Result<Self> {
+        if value.is_sign_positive() {
+            self.0.projectile.transverse_inertia = value;
+            Ok(self)
+        } else {
+            Err(Error::PositiveExpected {
+                value: value.get::<kilogram_square_meter>(),
+            })
+        }
+    }
+
+    pub fn set_coefficients(mut self, value: Coefficients) -> Self {
+        self.0.projectile.coefficients = value;
+        self
+    }
+}
+
+// At most one of the inputs giving the same quantity, by name and whether it was given
+fn exclusive(inputs: &[(&'static str, bool)]) -> Result<()> {
+    let mut given = inputs
+        .iter()
+        .filter(|&&(_, given)| given)
+        .map(|&(name, _)| name);
+    match (given.next(), given.next()) {
+        (Some(a), Some(b)) => Err(Error::Conflict {
+            first: a,
+            second: b,
+        }),
+        _ => Ok(()),
+    }
 }
@@ -3,12 +3,12 @@ use crate::Numeric;
 pub use uom::si::{
     acceleration, angle, angular_velocity, area, energy,
     f64::{
-        Acceleration, Angle, AngularVelocity, Area, ArealMassDensity, Energy, Length, Mass,
-        MassDensity, MolarHeatCapacity, MolarMass, Pressure, Ratio, ReciprocalLength,
-        ThermodynamicTemperature, Time, Velocity,
+        Acceleration, Angle, AngularVelocity, Area, ArealMassDensity, Energy, Frequency, Length,
+        Mass, MassDensity, MolarHeatCapacity, MolarMass, MomentOfInertia, Pressure, Ratio,
+        ReciprocalLength, TemperatureInterval, ThermodynamicTemperature, Time, Velocity,
     },
-    force, length, mass, mass_density, molar_mass, pressure, reciprocal_length,
-    thermodynamic_temperature, time, velocity,
+    force, frequency, length, mass, mass_density, molar_mass, moment_of_inertia, pressure, ratio,
+    reciprocal_length, temperature_interval, thermodynamic_temperature, time, velocity,
 };
 
 pub(crate) use uom::{
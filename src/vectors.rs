@@ -4,7 +4,10 @@ use crate::{
         Angle, ConstZero, Conversion, Dimension, ISQ, MyUnits, Num, Quantity, Units,
         angle::radian,
         quantity,
-        typenum::operator_aliases::{Diff, Sum},
+        typenum::{
+            Integer,
+            operator_aliases::{Diff, Sum},
+        },
     },
 };
 
@@ -115,6 +118,11 @@ pub trait Cross<Rhs = Self> {
     fn cross(&self, rhs: Rhs) -> Self::Output;
 }
 
+pub trait Dot<Rhs = Self> {
+    type Output;
+    fn dot(&self, rhs: Rhs) -> Self::Output;
+}
+
 pub trait Norm {
     type Output;
     fn norm(&self) -> Self::Output;
@@ -143,6 +151,29 @@ where
     }
 }
 
+impl<Dl: ?Sized, Dr: ?Sized, Ul: ?Sized, Ur: ?Sized, V> Dot<&DimVector3<Dr, Ur, V>>
+    for DimVector3<Dl, Ul, V>
+where
+    Dl: Dimension<
+            L: Add<Dr::L, Output: Integer>,
+            M: Add<Dr::M, Output: Integer>,
+            T: Add<Dr::T, Output: Integer>,
+            I: Add<Dr::I, Output: Integer>,
+            Th: Add<Dr::Th, Output: Integer>,
+            N: Add<Dr::N, Output: Integer>,
+            J: Add<Dr::J, Output: Integer>,
+        >,
+    Dr: Dimension,
+    Ul: Units<V>,
+    Ur: Units<V>,
+    V: Num + Conversion<V> + Scalar + Copy + ClosedAddAssign + ClosedMulAssign + ClosedSubAssign,
+{
+    type Output = Quantity<SumDimension<Dl, Dr>, Ul, V>;
+    fn dot(&self, rhs: &DimVector3<Dr, Ur, V>) -> Self::Output {
+        quantity!(self.value.dot(&rhs.value))
+    }
+}
+
 impl<D: ?Sized, U: ?Sized, V> Norm for DimVector3<D, U, V>
 where
     D: Dimension,